@@ -8,6 +8,9 @@
 //!
 //! See [RustCrypto/AEADs] for cipher implementations which use this trait.
 //!
+//! Keys, nonces and tags are [`Array`] types. With the `serde` feature enabled,
+//! they can be (de)serialized using `#[serde(with = "aead::array::serde")]`.
+//!
 //! [Authenticated Encryption with Associated Data]: https://en.wikipedia.org/wiki/Authenticated_encryption
 //! [chosen-ciphertext attacks]: https://en.wikipedia.org/wiki/Chosen-ciphertext_attack
 //! [ciphertext indistinguishability]: https://en.wikipedia.org/wiki/Ciphertext_indistinguishability
@@ -37,7 +40,9 @@ pub mod dev;
 #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
 pub mod stream;
 
-use array::Array;
+pub use array;
+pub use crypto_common::InvalidLength;
+
 #[cfg(feature = "heapless")]
 #[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
 pub use heapless;
@@ -46,7 +51,9 @@ pub use heapless;
 #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
 pub use rand_core;
 
-use core::{convert::TryInto, fmt};
+use array::Array;
+use core::fmt;
+use crypto_common::Lengths;
 
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
@@ -83,13 +90,14 @@ pub trait NewAead {
 
     /// Create new AEAD instance from key given as a byte slice..
     ///
-    /// Default implementation will accept only keys with length equal to `KeySize`.
-    fn new_from_slice(key: &[u8]) -> Result<Self>
+    /// Default implementation will accept only keys with length equal to `Key::LEN`.
+    fn new_from_slice(key: &[u8]) -> core::result::Result<Self, InvalidLength>
     where
         Self: Sized,
     {
-        let key = key.try_into().map_err(|_| Error)?;
-        Ok(Self::new(key))
+        Self::Key::try_copy_from_slice(key)
+            .map(Self::new)
+            .map_err(|_| InvalidLength::key(Lengths::fixed(Self::Key::LEN), key.len()))
     }
 
     /// Generate a random key for this AEAD using the provided [`CryptoRng`].
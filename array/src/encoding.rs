@@ -0,0 +1,839 @@
+//! Constant-time hex and Base64 encoding of byte arrays.
+//!
+//! Encoding and decoding do not allocate and do not branch or index memory
+//! depending on the encoded data. The only data-dependent branches are error
+//! returns after the whole input has been processed.
+
+use crate::Array;
+use core::{fmt, str::FromStr};
+
+/// Error returned when a buffer has an invalid length.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct LengthError {
+    /// Expected length.
+    pub expected: usize,
+    /// Actual length.
+    pub actual: usize,
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid length: expected {}, got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthError {}
+
+/// Error returned by hex and Base64 decoding.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// Length of encoded string does not correspond to the array length.
+    InvalidLength(LengthError),
+    /// Encoded string contains invalid characters or is not canonical.
+    InvalidEncoding,
+}
+
+impl From<LengthError> for DecodeError {
+    #[inline]
+    fn from(err: LengthError) -> Self {
+        Self::InvalidLength(err)
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(err) => write!(f, "decode error: {}", err),
+            Self::InvalidEncoding => f.write_str("decode error: invalid encoding"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidLength(err) => Some(err),
+            Self::InvalidEncoding => None,
+        }
+    }
+}
+
+/// Hex and Base64 encoding methods of byte arrays.
+///
+/// This trait is implemented for all [`Array`] types.
+pub trait ArrayEncoding: Array {
+    /// Decode array from a hex string. Both lower and upper case
+    /// characters are accepted.
+    ///
+    /// ```
+    /// use array::ArrayEncoding;
+    ///
+    /// let key = <[u8; 4]>::from_hex("DEADbeef").unwrap();
+    /// assert_eq!(key, [0xde, 0xad, 0xbe, 0xef]);
+    /// assert!(<[u8; 4]>::from_hex("deadbe").is_err());
+    /// ```
+    fn from_hex(s: &str) -> Result<Self, DecodeError> {
+        let src = s.as_bytes();
+        check_len(hex_len(Self::LEN), src.len())?;
+        let mut res = Self::zero();
+        let mut err = 0;
+        for (dst, src) in res.as_mut().iter_mut().zip(src.chunks_exact(2)) {
+            let hi = decode_nibble(src[0]);
+            let lo = decode_nibble(src[1]);
+            err |= hi | lo;
+            *dst = ((hi << 4) | lo) as u8;
+        }
+        if err < 0 {
+            return Err(DecodeError::InvalidEncoding);
+        }
+        Ok(res)
+    }
+
+    /// Decode array from a padded Base64 string using the standard alphabet.
+    ///
+    /// ```
+    /// use array::ArrayEncoding;
+    ///
+    /// let iv = <[u8; 4]>::from_base64("3q2+7w==").unwrap();
+    /// assert_eq!(iv, [0xde, 0xad, 0xbe, 0xef]);
+    /// ```
+    fn from_base64(s: &str) -> Result<Self, DecodeError> {
+        decode_base64(s, &STANDARD)
+    }
+
+    /// Decode array from an unpadded Base64 string using the URL-safe alphabet.
+    ///
+    /// ```
+    /// use array::ArrayEncoding;
+    ///
+    /// let iv = <[u8; 4]>::from_base64_url("3q2-7w").unwrap();
+    /// assert_eq!(iv, [0xde, 0xad, 0xbe, 0xef]);
+    /// ```
+    fn from_base64_url(s: &str) -> Result<Self, DecodeError> {
+        decode_base64(s, &URL_SAFE)
+    }
+
+    /// Encode array as a lower case hex string into `buf`.
+    ///
+    /// Returns an error if `buf` is smaller than `2 * Self::LEN`.
+    fn encode_hex_lower<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, LengthError> {
+        encode_hex(self.as_ref(), buf, LOWER)
+    }
+
+    /// Encode array as an upper case hex string into `buf`.
+    ///
+    /// Returns an error if `buf` is smaller than `2 * Self::LEN`.
+    fn encode_hex_upper<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, LengthError> {
+        encode_hex(self.as_ref(), buf, UPPER)
+    }
+
+    /// Encode array as a padded Base64 string using the standard alphabet.
+    ///
+    /// Returns an error if `buf` is smaller than the encoded length.
+    fn encode_base64<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, LengthError> {
+        encode_base64(self.as_ref(), buf, &STANDARD)
+    }
+
+    /// Encode array as an unpadded Base64 string using the URL-safe alphabet.
+    ///
+    /// Returns an error if `buf` is smaller than the encoded length.
+    fn encode_base64_url<'a>(&self, buf: &'a mut [u8]) -> Result<&'a str, LengthError> {
+        encode_base64(self.as_ref(), buf, &URL_SAFE)
+    }
+
+    /// Get wrapper implementing [`fmt::LowerHex`] and [`fmt::UpperHex`].
+    ///
+    /// ```
+    /// use array::ArrayEncoding;
+    ///
+    /// let out = [0xde, 0xad, 0xbe, 0xefu8];
+    /// assert_eq!(format!("{:x}", out.hex()), "deadbeef");
+    /// assert_eq!(format!("{:X}", out.hex()), "DEADBEEF");
+    /// ```
+    fn hex(&self) -> Hex<&Self> {
+        Hex(self)
+    }
+
+    /// Get wrapper implementing [`fmt::Display`] using standard Base64.
+    fn base64(&self) -> Base64<&Self> {
+        Base64(self)
+    }
+
+    /// Get wrapper implementing [`fmt::Display`] using URL-safe Base64.
+    fn base64_url(&self) -> Base64Url<&Self> {
+        Base64Url(self)
+    }
+}
+
+impl<A: Array> ArrayEncoding for A {}
+
+/// Wrapper which formats and parses bytes as hex.
+///
+/// Implements [`fmt::LowerHex`], [`fmt::UpperHex`] and [`fmt::Display`]
+/// (lower case) for any byte container, and [`FromStr`] for [`Array`] types.
+/// Width, fill, alignment and the `#` and `0` flags are honoured, as for
+/// integers formatted with `{:x}`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Hex<A>(pub A);
+
+/// Wrapper which formats and parses bytes as padded standard Base64.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Base64<A>(pub A);
+
+/// Wrapper which formats and parses bytes as unpadded URL-safe Base64.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Base64Url<A>(pub A);
+
+impl<A: AsRef<[u8]>> Hex<A> {
+    fn fmt_case(&self, f: &mut fmt::Formatter<'_>, case: u8) -> fmt::Result {
+        let src = self.0.as_ref();
+        let prefix = if f.alternate() { "0x" } else { "" };
+        fmt_padded(f, prefix, hex_len(src.len()), |f| {
+            let mut buf = [0u8; 64];
+            for chunk in src.chunks(buf.len() / 2) {
+                let s = encode_hex(chunk, &mut buf, case).map_err(|_| fmt::Error)?;
+                f.write_str(s)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<A: AsRef<[u8]>> fmt::LowerHex for Hex<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_case(f, LOWER)
+    }
+}
+
+impl<A: AsRef<[u8]>> fmt::UpperHex for Hex<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_case(f, UPPER)
+    }
+}
+
+impl<A: AsRef<[u8]>> fmt::Display for Hex<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_case(f, LOWER)
+    }
+}
+
+impl<A: Array> FromStr for Hex<A> {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, DecodeError> {
+        A::from_hex(s).map(Self)
+    }
+}
+
+impl<A: AsRef<[u8]>> fmt::Display for Base64<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_base64(self.0.as_ref(), f, &STANDARD)
+    }
+}
+
+impl<A: Array> FromStr for Base64<A> {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, DecodeError> {
+        A::from_base64(s).map(Self)
+    }
+}
+
+impl<A: AsRef<[u8]>> fmt::Display for Base64Url<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_base64(self.0.as_ref(), f, &URL_SAFE)
+    }
+}
+
+impl<A: Array> FromStr for Base64Url<A> {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, DecodeError> {
+        A::from_base64_url(s).map(Self)
+    }
+}
+
+/// Offset added to nibbles above 9 for the lower case alphabet.
+const LOWER: u8 = 0x27;
+/// Offset added to nibbles above 9 for the upper case alphabet.
+const UPPER: u8 = 0x07;
+
+#[inline(always)]
+fn check_len(expected: usize, actual: usize) -> Result<(), LengthError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(LengthError { expected, actual })
+    }
+}
+
+#[inline(always)]
+fn hex_len(n: usize) -> usize {
+    2 * n
+}
+
+/// Decode hex character, returns -1 for invalid characters.
+#[inline(always)]
+fn decode_nibble(src: u8) -> i16 {
+    let ch = src as i16;
+    let mut ret: i16 = -1;
+    // 0-9: 0x30..=0x39
+    ret += (((0x2f - ch) & (ch - 0x3a)) >> 8) & (ch - 0x2f);
+    // A-F: 0x41..=0x46
+    ret += (((0x40 - ch) & (ch - 0x47)) >> 8) & (ch - 0x36);
+    // a-f: 0x61..=0x66
+    ret += (((0x60 - ch) & (ch - 0x67)) >> 8) & (ch - 0x56);
+    ret
+}
+
+/// Encode nibble as a hex character using the alphabet selected by `case`.
+#[inline(always)]
+fn encode_nibble(src: u8, case: u8) -> u8 {
+    let n = src as i16;
+    // add alphabet offset if nibble is bigger than 9
+    (n + 0x30 + (((9 - n) >> 8) & case as i16)) as u8
+}
+
+fn encode_hex<'a>(src: &[u8], buf: &'a mut [u8], case: u8) -> Result<&'a str, LengthError> {
+    let len = hex_len(src.len());
+    if buf.len() < len {
+        return Err(LengthError {
+            expected: len,
+            actual: buf.len(),
+        });
+    }
+    let dst = &mut buf[..len];
+    for (src, dst) in src.iter().zip(dst.chunks_exact_mut(2)) {
+        dst[0] = encode_nibble(src >> 4, case);
+        dst[1] = encode_nibble(src & 0x0f, case);
+    }
+    Ok(ascii_str(dst))
+}
+
+/// Base64 alphabet, defined by the characters used for values 62 and 63.
+struct Alphabet {
+    c62: u8,
+    c63: u8,
+    padded: bool,
+}
+
+const STANDARD: Alphabet = Alphabet {
+    c62: b'+',
+    c63: b'/',
+    padded: true,
+};
+
+const URL_SAFE: Alphabet = Alphabet {
+    c62: b'-',
+    c63: b'_',
+    padded: false,
+};
+
+impl Alphabet {
+    #[inline(always)]
+    fn encoded_len(&self, n: usize) -> usize {
+        if self.padded {
+            4 * ((n + 2) / 3)
+        } else {
+            (4 * n + 2) / 3
+        }
+    }
+
+    /// Decode Base64 character, returns -1 for invalid characters.
+    #[inline(always)]
+    fn decode_6bits(&self, src: u8) -> i16 {
+        let ch = src as i16;
+        let c62 = self.c62 as i16;
+        let c63 = self.c63 as i16;
+        let mut ret: i16 = -1;
+        // A-Z: 0x41..=0x5a
+        ret += (((0x40 - ch) & (ch - 0x5b)) >> 8) & (ch - 0x40);
+        // a-z: 0x61..=0x7a
+        ret += (((0x60 - ch) & (ch - 0x7b)) >> 8) & (ch - 0x46);
+        // 0-9: 0x30..=0x39
+        ret += (((0x2f - ch) & (ch - 0x3a)) >> 8) & (ch + 0x05);
+        ret += (((c62 - 1 - ch) & (ch - c62 - 1)) >> 8) & 63;
+        ret += (((c63 - 1 - ch) & (ch - c63 - 1)) >> 8) & 64;
+        ret
+    }
+
+    /// Encode 6-bit value as a Base64 character.
+    #[inline(always)]
+    fn encode_6bits(&self, src: u8) -> u8 {
+        let n = src as i16;
+        let mut diff: i16 = 0x41;
+        // a-z
+        diff += ((25 - n) >> 8) & 0x06;
+        // 0-9
+        diff -= ((51 - n) >> 8) & 0x4b;
+        // value 62
+        diff += ((61 - n) >> 8) & (self.c62 as i16 - 0x3a);
+        // value 63
+        diff += ((62 - n) >> 8) & (self.c63 as i16 - self.c62 as i16 - 1);
+        (n + diff) as u8
+    }
+}
+
+fn decode_base64<A: Array>(s: &str, alphabet: &Alphabet) -> Result<A, DecodeError> {
+    let src = s.as_bytes();
+    check_len(alphabet.encoded_len(A::LEN), src.len())?;
+
+    let mut res = A::zero();
+    let dst = res.as_mut();
+    let mut err: i16 = 0;
+
+    let (dst_full, dst_tail) = dst.split_at_mut(3 * (A::LEN / 3));
+    let (src_full, src_tail) = src.split_at(4 * (A::LEN / 3));
+    for (dst, src) in dst_full.chunks_exact_mut(3).zip(src_full.chunks_exact(4)) {
+        let c0 = alphabet.decode_6bits(src[0]);
+        let c1 = alphabet.decode_6bits(src[1]);
+        let c2 = alphabet.decode_6bits(src[2]);
+        let c3 = alphabet.decode_6bits(src[3]);
+        err |= c0 | c1 | c2 | c3;
+        dst[0] = ((c0 << 2) | (c1 >> 4)) as u8;
+        dst[1] = ((c1 << 4) | (c2 >> 2)) as u8;
+        dst[2] = ((c2 << 6) | c3) as u8;
+    }
+
+    let pad = match dst_tail.len() {
+        1 => {
+            let c0 = alphabet.decode_6bits(src_tail[0]);
+            let c1 = alphabet.decode_6bits(src_tail[1]);
+            err |= c0 | c1;
+            // unused bits must be zero
+            err |= -(c1 & 0x0f);
+            dst_tail[0] = ((c0 << 2) | (c1 >> 4)) as u8;
+            &src_tail[2..]
+        }
+        2 => {
+            let c0 = alphabet.decode_6bits(src_tail[0]);
+            let c1 = alphabet.decode_6bits(src_tail[1]);
+            let c2 = alphabet.decode_6bits(src_tail[2]);
+            err |= c0 | c1 | c2;
+            // unused bits must be zero
+            err |= -(c2 & 0x03);
+            dst_tail[0] = ((c0 << 2) | (c1 >> 4)) as u8;
+            dst_tail[1] = ((c1 << 4) | (c2 >> 2)) as u8;
+            &src_tail[3..]
+        }
+        _ => src_tail,
+    };
+    for &ch in pad {
+        err |= -((ch ^ b'=') as i16);
+    }
+
+    if err < 0 {
+        return Err(DecodeError::InvalidEncoding);
+    }
+    Ok(res)
+}
+
+fn encode_base64<'a>(
+    src: &[u8],
+    buf: &'a mut [u8],
+    alphabet: &Alphabet,
+) -> Result<&'a str, LengthError> {
+    let len = alphabet.encoded_len(src.len());
+    if buf.len() < len {
+        return Err(LengthError {
+            expected: len,
+            actual: buf.len(),
+        });
+    }
+    let dst = &mut buf[..len];
+
+    let (src_full, src_tail) = src.split_at(3 * (src.len() / 3));
+    let (dst_full, dst_tail) = dst.split_at_mut(4 * (src.len() / 3));
+    for (src, dst) in src_full.chunks_exact(3).zip(dst_full.chunks_exact_mut(4)) {
+        dst[0] = alphabet.encode_6bits(src[0] >> 2);
+        dst[1] = alphabet.encode_6bits(((src[0] << 4) | (src[1] >> 4)) & 0x3f);
+        dst[2] = alphabet.encode_6bits(((src[1] << 2) | (src[2] >> 6)) & 0x3f);
+        dst[3] = alphabet.encode_6bits(src[2] & 0x3f);
+    }
+
+    let pad = match src_tail.len() {
+        1 => {
+            dst_tail[0] = alphabet.encode_6bits(src_tail[0] >> 2);
+            dst_tail[1] = alphabet.encode_6bits((src_tail[0] << 4) & 0x3f);
+            &mut dst_tail[2..]
+        }
+        2 => {
+            dst_tail[0] = alphabet.encode_6bits(src_tail[0] >> 2);
+            dst_tail[1] = alphabet.encode_6bits(((src_tail[0] << 4) | (src_tail[1] >> 4)) & 0x3f);
+            dst_tail[2] = alphabet.encode_6bits((src_tail[1] << 2) & 0x3f);
+            &mut dst_tail[3..]
+        }
+        _ => dst_tail,
+    };
+    pad.iter_mut().for_each(|b| *b = b'=');
+
+    Ok(ascii_str(dst))
+}
+
+fn fmt_base64(src: &[u8], f: &mut fmt::Formatter<'_>, alphabet: &Alphabet) -> fmt::Result {
+    fmt_padded(f, "", alphabet.encoded_len(src.len()), |f| {
+        // 48 bytes are encoded into 64 characters without padding
+        let mut buf = [0u8; 64];
+        for chunk in src.chunks(48) {
+            let s = encode_base64(chunk, &mut buf, alphabet).map_err(|_| fmt::Error)?;
+            f.write_str(s)?;
+        }
+        Ok(())
+    })
+}
+
+/// Write `prefix` followed by `len` characters produced by `body`, honouring
+/// the width, fill, alignment and `0` flag of `f`.
+fn fmt_padded(
+    f: &mut fmt::Formatter<'_>,
+    prefix: &str,
+    len: usize,
+    body: impl FnOnce(&mut fmt::Formatter<'_>) -> fmt::Result,
+) -> fmt::Result {
+    let pad = f
+        .width()
+        .map_or(0, |w| w.saturating_sub(prefix.len() + len));
+    if f.sign_aware_zero_pad() {
+        f.write_str(prefix)?;
+        write_fill(f, '0', pad)?;
+        return body(f);
+    }
+    let (pre, post) = match f.align() {
+        Some(fmt::Alignment::Right) => (pad, 0),
+        Some(fmt::Alignment::Center) => (pad / 2, pad - pad / 2),
+        Some(fmt::Alignment::Left) | None => (0, pad),
+    };
+    let fill = f.fill();
+    write_fill(f, fill, pre)?;
+    f.write_str(prefix)?;
+    body(f)?;
+    write_fill(f, fill, post)
+}
+
+fn write_fill(f: &mut fmt::Formatter<'_>, fill: char, n: usize) -> fmt::Result {
+    (0..n).try_for_each(|_| fmt::Write::write_char(f, fill))
+}
+
+#[inline(always)]
+fn ascii_str(buf: &[u8]) -> &str {
+    debug_assert!(buf.is_ascii());
+    // SAFETY: encoders write only ASCII characters into `buf`
+    unsafe { core::str::from_utf8_unchecked(buf) }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::{format, string::String};
+
+    const DEADBEEF: [u8; 4] = [0xde, 0xad, 0xbe, 0xef];
+
+    fn replace(s: &str, i: usize, ch: char) -> String {
+        s.char_indices()
+            .map(|(j, c)| if i == j { ch } else { c })
+            .collect()
+    }
+
+    #[test]
+    fn hex_roundtrip() {
+        let mut buf = [0u8; 8];
+        assert_eq!(DEADBEEF.encode_hex_lower(&mut buf), Ok("deadbeef"));
+        assert_eq!(DEADBEEF.encode_hex_upper(&mut buf), Ok("DEADBEEF"));
+        assert_eq!(<[u8; 4]>::from_hex("deadbeef"), Ok(DEADBEEF));
+        assert_eq!(<[u8; 4]>::from_hex("DeAdBeEf"), Ok(DEADBEEF));
+        assert_eq!(<[u8; 0]>::from_hex(""), Ok([]));
+
+        let all: [u8; 256] = {
+            let mut a = [0u8; 256];
+            a.iter_mut().enumerate().for_each(|(i, b)| *b = i as u8);
+            a
+        };
+        let s = format!("{}", all.hex());
+        assert_eq!(<[u8; 256]>::from_hex(&s), Ok(all));
+        assert_eq!(<[u8; 256]>::from_hex(&s.to_uppercase()), Ok(all));
+    }
+
+    #[test]
+    fn hex_invalid_char_at_every_position() {
+        let valid = "deadbeef";
+        for i in 0..valid.len() {
+            for &ch in &['g', 'G', 'x', ' ', '/', ':', '@', '`', '\0', '\u{7f}'] {
+                let s = replace(valid, i, ch);
+                assert_eq!(
+                    <[u8; 4]>::from_hex(&s),
+                    Err(DecodeError::InvalidEncoding),
+                    "{:?}",
+                    s
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hex_invalid_length() {
+        for &(s, actual) in &[("", 0), ("d", 1), ("deadbee", 7), ("deadbeef0", 9)] {
+            assert_eq!(
+                <[u8; 4]>::from_hex(s),
+                Err(DecodeError::InvalidLength(LengthError {
+                    expected: 8,
+                    actual
+                }))
+            );
+        }
+        // length is checked before the characters
+        assert_eq!(
+            <[u8; 4]>::from_hex("zz"),
+            Err(DecodeError::InvalidLength(LengthError {
+                expected: 8,
+                actual: 2
+            }))
+        );
+
+        let mut buf = [0u8; 7];
+        let err = LengthError {
+            expected: 8,
+            actual: 7,
+        };
+        assert_eq!(DEADBEEF.encode_hex_lower(&mut buf), Err(err));
+        assert_eq!(DEADBEEF.encode_hex_upper(&mut buf), Err(err));
+    }
+
+    #[test]
+    fn base64_padding() {
+        let mut buf = [0u8; 8];
+        assert_eq!([0u8; 1].encode_base64(&mut buf), Ok("AA=="));
+        assert_eq!([0u8; 2].encode_base64(&mut buf), Ok("AAA="));
+        assert_eq!([0u8; 3].encode_base64(&mut buf), Ok("AAAA"));
+        assert_eq!([0u8; 1].encode_base64_url(&mut buf), Ok("AA"));
+        assert_eq!([0u8; 2].encode_base64_url(&mut buf), Ok("AAA"));
+        assert_eq!([0u8; 3].encode_base64_url(&mut buf), Ok("AAAA"));
+
+        assert_eq!(<[u8; 1]>::from_base64("AA=="), Ok([0]));
+        assert_eq!(<[u8; 2]>::from_base64("AAA="), Ok([0; 2]));
+        assert_eq!(<[u8; 0]>::from_base64(""), Ok([]));
+
+        // padding replaced by data characters
+        assert_eq!(
+            <[u8; 1]>::from_base64("AAA="),
+            Err(DecodeError::InvalidEncoding)
+        );
+        assert_eq!(
+            <[u8; 1]>::from_base64("AA=A"),
+            Err(DecodeError::InvalidEncoding)
+        );
+        assert_eq!(
+            <[u8; 2]>::from_base64("AAAA"),
+            Err(DecodeError::InvalidEncoding)
+        );
+        // padding in place of data characters
+        assert_eq!(
+            <[u8; 1]>::from_base64("A==="),
+            Err(DecodeError::InvalidEncoding)
+        );
+        assert_eq!(
+            <[u8; 2]>::from_base64("AA=="),
+            Err(DecodeError::InvalidEncoding)
+        );
+        assert_eq!(
+            <[u8; 4]>::from_base64("AA==AA=="),
+            Err(DecodeError::InvalidEncoding)
+        );
+        // non-zero unused bits are not canonical
+        assert_eq!(
+            <[u8; 1]>::from_base64("AB=="),
+            Err(DecodeError::InvalidEncoding)
+        );
+        assert_eq!(
+            <[u8; 2]>::from_base64("AAB="),
+            Err(DecodeError::InvalidEncoding)
+        );
+        assert_eq!(
+            <[u8; 1]>::from_base64_url("AB"),
+            Err(DecodeError::InvalidEncoding)
+        );
+        assert_eq!(
+            <[u8; 2]>::from_base64_url("AAB"),
+            Err(DecodeError::InvalidEncoding)
+        );
+        // missing padding
+        assert_eq!(
+            <[u8; 1]>::from_base64("AA"),
+            Err(DecodeError::InvalidLength(LengthError {
+                expected: 4,
+                actual: 2
+            }))
+        );
+    }
+
+    #[test]
+    fn base64_url_alphabet() {
+        let v = [0xfb, 0xff, 0xbf];
+        let mut buf = [0u8; 4];
+        assert_eq!(v.encode_base64(&mut buf), Ok("+/+/"));
+        assert_eq!(v.encode_base64_url(&mut buf), Ok("-_-_"));
+        assert_eq!(<[u8; 3]>::from_base64("+/+/"), Ok(v));
+        assert_eq!(<[u8; 3]>::from_base64_url("-_-_"), Ok(v));
+
+        // alphabets must not be mixed
+        for s in &["-_-_", "+_+_", "-/-/"] {
+            assert_eq!(<[u8; 3]>::from_base64(s), Err(DecodeError::InvalidEncoding));
+        }
+        for s in &["+/+/", "+_-_", "-_-/"] {
+            assert_eq!(
+                <[u8; 3]>::from_base64_url(s),
+                Err(DecodeError::InvalidEncoding)
+            );
+        }
+
+        // URL-safe encoding is unpadded
+        assert_eq!(<[u8; 4]>::from_base64_url("3q2-7w"), Ok(DEADBEEF));
+        assert_eq!(
+            <[u8; 4]>::from_base64_url("3q2-7w=="),
+            Err(DecodeError::InvalidLength(LengthError {
+                expected: 6,
+                actual: 8
+            }))
+        );
+    }
+
+    #[test]
+    fn base64_invalid_char_at_every_position() {
+        let v = [0x12, 0x34, 0x56, 0x78, 0x9a];
+        let mut buf = [0u8; 8];
+        let std = String::from(v.encode_base64(&mut buf).unwrap());
+        let url = String::from(v.encode_base64_url(&mut buf).unwrap());
+        assert_eq!(<[u8; 5]>::from_base64(&std), Ok(v));
+        assert_eq!(<[u8; 5]>::from_base64_url(&url), Ok(v));
+
+        for i in 0..std.len() {
+            for &ch in &['*', '.', ' ', '@', '[', '`', '{', '\0', '-', '_'] {
+                let s = replace(&std, i, ch);
+                assert_eq!(
+                    <[u8; 5]>::from_base64(&s),
+                    Err(DecodeError::InvalidEncoding),
+                    "{:?}",
+                    s
+                );
+            }
+        }
+        // the last character is padding
+        for i in 0..std.len() - 1 {
+            let s = replace(&std, i, '=');
+            assert_eq!(
+                <[u8; 5]>::from_base64(&s),
+                Err(DecodeError::InvalidEncoding),
+                "{:?}",
+                s
+            );
+        }
+        for i in 0..url.len() {
+            for &ch in &['*', '.', ' ', '@', '[', '`', '{', '\0', '+', '/', '='] {
+                let s = replace(&url, i, ch);
+                assert_eq!(
+                    <[u8; 5]>::from_base64_url(&s),
+                    Err(DecodeError::InvalidEncoding),
+                    "{:?}",
+                    s
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn base64_invalid_length() {
+        assert_eq!(
+            <[u8; 4]>::from_base64("3q2+"),
+            Err(DecodeError::InvalidLength(LengthError {
+                expected: 8,
+                actual: 4
+            }))
+        );
+        assert_eq!(
+            <[u8; 4]>::from_base64_url("3q2-7"),
+            Err(DecodeError::InvalidLength(LengthError {
+                expected: 6,
+                actual: 5
+            }))
+        );
+        let mut buf = [0u8; 7];
+        assert_eq!(
+            DEADBEEF.encode_base64(&mut buf),
+            Err(LengthError {
+                expected: 8,
+                actual: 7
+            })
+        );
+        let mut buf = [0u8; 5];
+        assert_eq!(
+            DEADBEEF.encode_base64_url(&mut buf),
+            Err(LengthError {
+                expected: 6,
+                actual: 5
+            })
+        );
+    }
+
+    #[test]
+    fn wrappers_from_str() {
+        assert_eq!("deadbeef".parse::<Hex<[u8; 4]>>(), Ok(Hex(DEADBEEF)));
+        assert_eq!("3q2+7w==".parse::<Base64<[u8; 4]>>(), Ok(Base64(DEADBEEF)));
+        assert_eq!(
+            "3q2-7w".parse::<Base64Url<[u8; 4]>>(),
+            Ok(Base64Url(DEADBEEF))
+        );
+        assert!("3q2+7w".parse::<Base64Url<[u8; 4]>>().is_err());
+    }
+
+    #[test]
+    fn display_formatting() {
+        let h = DEADBEEF.hex();
+        assert_eq!(format!("{}", h), "deadbeef");
+        assert_eq!(format!("{:X}", h), "DEADBEEF");
+        assert_eq!(format!("{:#x}", h), "0xdeadbeef");
+        assert_eq!(format!("{:12}", h), "deadbeef    ");
+        assert_eq!(format!("{:>12}", h), "    deadbeef");
+        assert_eq!(format!("{:*^13}", h), "**deadbeef***");
+        assert_eq!(format!("{:>#12X}", h), "  0xDEADBEEF");
+        assert_eq!(format!("{:012x}", h), "0000deadbeef");
+        assert_eq!(format!("{:#012x}", h), "0x00deadbeef");
+        assert_eq!(format!("{:4}", h), "deadbeef");
+
+        assert_eq!(format!("{}", DEADBEEF.base64()), "3q2+7w==");
+        assert_eq!(format!("{:>10}", DEADBEEF.base64()), "  3q2+7w==");
+        assert_eq!(format!("{:-<8}", DEADBEEF.base64_url()), "3q2-7w--");
+
+        // values longer than the internal formatting buffers
+        let long = [0xabu8; 100];
+        let s = format!("{:>210}", long.hex());
+        assert_eq!(s.len(), 210);
+        assert!(s.starts_with("          abab"));
+        assert_eq!(<[u8; 100]>::from_hex(s.trim_start()), Ok(long));
+        let s = format!("{}", long.base64());
+        assert_eq!(<[u8; 100]>::from_base64(&s), Ok(long));
+    }
+
+    #[test]
+    fn error_display() {
+        let err = LengthError {
+            expected: 8,
+            actual: 7,
+        };
+        assert_eq!(format!("{}", err), "invalid length: expected 8, got 7");
+        assert_eq!(
+            format!("{}", DecodeError::from(err)),
+            "decode error: invalid length: expected 8, got 7"
+        );
+        assert_eq!(
+            format!("{}", DecodeError::InvalidEncoding),
+            "decode error: invalid encoding"
+        );
+    }
+}
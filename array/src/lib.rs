@@ -1,27 +1,218 @@
-use std::{array::TryFromSliceError, convert::TryFrom};
+//! Fixed-size array traits built on top of const generics.
+
+#![no_std]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![warn(missing_docs, rust_2018_idioms)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod encoding;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde;
+
+pub use encoding::{ArrayEncoding, Base64, Base64Url, DecodeError, Hex};
+
+use core::{array::TryFromSliceError, convert::TryFrom, marker::PhantomData, slice};
+
+/// Types which have an all-zero value.
+///
+/// Implemented for primitive integers and for arrays of such types,
+/// e.g. `[u8; 16]` or `[[u8; 16]; 8]`.
+pub trait Zero: Copy + 'static {
+    /// The all-zero value.
+    const ZERO: Self;
+}
+
+macro_rules! impl_zero {
+    ($($t:ty)*) => {
+        $( impl Zero for $t { const ZERO: Self = 0; } )*
+    };
+}
+
+impl_zero! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
+
+impl<T: Zero, const N: usize> Zero for [T; N] {
+    const ZERO: Self = [T::ZERO; N];
+}
 
 /// Array type
-pub trait Array: for<'a> TryFrom<&'a [u8], Error = TryFromSliceError> + AsRef<[u8]> + AsMut<[u8]> + Copy {
+///
+/// # Safety
+/// Implementors MUST have exactly the same memory layout as `[T; Self::LEN]`,
+/// i.e. size of `Self::LEN * size_of::<T>()`, alignment of `T` and elements
+/// stored contiguously without padding. `AsRef` and `AsMut` MUST return
+/// slices covering the whole array.
+///
+/// Code which works with blocks (e.g. `block-buffer`) relies on this guarantee
+/// to cast byte slices to slices of `Array<u8>` implementors, which always
+/// have alignment of 1.
+pub unsafe trait Array<T: Zero = u8>: Zero + AsRef<[T]> + AsMut<[T]> {
     /// Length of the array
     const LEN: usize;
+
     /// Get the zero value for the array
-    fn zero() -> Self;
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    /// Create array where each element is initialized with `f(index)`.
+    #[inline]
+    fn from_fn(mut f: impl FnMut(usize) -> T) -> Self {
+        let mut res = Self::ZERO;
+        for (i, v) in res.as_mut().iter_mut().enumerate() {
+            *v = f(i);
+        }
+        res
+    }
+
+    /// Cast slice to an array reference.
+    ///
+    /// Returns an error if length of `slice` is not equal to `Self::LEN`.
+    fn try_from_slice(slice: &[T]) -> Result<&Self, TryFromSliceError>;
+
+    /// Cast mutable slice to a mutable array reference.
+    ///
+    /// Returns an error if length of `slice` is not equal to `Self::LEN`.
+    fn try_from_mut_slice(slice: &mut [T]) -> Result<&mut Self, TryFromSliceError>;
 
-    fn try_from_slice(slice: &[u8]) -> Result<&Self, TryFromSliceError>;
-    fn try_from_mut_slice(slice: &mut [u8]) -> Result<&mut Self, TryFromSliceError>;
+    /// Copy slice into a new array.
+    ///
+    /// Returns an error if length of `slice` is not equal to `Self::LEN`.
+    #[inline]
+    fn try_copy_from_slice(slice: &[T]) -> Result<Self, TryFromSliceError> {
+        let arr = Self::try_from_slice(slice)?;
+        Ok(*arr)
+    }
+
+    /// Split slice into a slice of arrays and a tail with
+    /// less than `Self::LEN` elements.
+    ///
+    /// If `Self::LEN` is equal to zero, returns empty slice of arrays.
+    #[inline]
+    fn slice_as_chunks(buf: &[T]) -> (&[Self], &[T]) {
+        if Self::LEN == 0 {
+            return (&[], buf);
+        }
+        let chunks_len = buf.len() / Self::LEN;
+        let (chunks, tail) = buf.split_at(chunks_len * Self::LEN);
+        // SAFETY: `chunks` contains exactly `chunks_len * Self::LEN` elements
+        // and `Self` has the same layout as `[T; Self::LEN]`
+        let chunks = unsafe { slice::from_raw_parts(chunks.as_ptr() as *const Self, chunks_len) };
+        (chunks, tail)
+    }
+
+    /// Split mutable slice into a slice of arrays and a tail with
+    /// less than `Self::LEN` elements.
+    ///
+    /// If `Self::LEN` is equal to zero, returns empty slice of arrays.
+    #[inline]
+    fn slice_as_chunks_mut(buf: &mut [T]) -> (&mut [Self], &mut [T]) {
+        if Self::LEN == 0 {
+            return (&mut [], buf);
+        }
+        let chunks_len = buf.len() / Self::LEN;
+        let (chunks, tail) = buf.split_at_mut(chunks_len * Self::LEN);
+        // SAFETY: `chunks` contains exactly `chunks_len * Self::LEN` elements
+        // and `Self` has the same layout as `[T; Self::LEN]`
+        let chunks =
+            unsafe { slice::from_raw_parts_mut(chunks.as_mut_ptr() as *mut Self, chunks_len) };
+        (chunks, tail)
+    }
+
+    /// Split array into two arrays.
+    ///
+    /// Length of `Self` MUST be equal to the sum of lengths of `A` and `B`.
+    /// This is checked at compile time, so this method never panics.
+    ///
+    /// ```
+    /// use array::Array;
+    ///
+    /// let key = [1u8, 2, 3, 4, 5, 6];
+    /// let (k1, k2): ([u8; 2], [u8; 4]) = key.split();
+    /// assert_eq!(k1, [1, 2]);
+    /// assert_eq!(k2, [3, 4, 5, 6]);
+    /// ```
+    #[inline]
+    fn split<A: Array<T>, B: Array<T>>(self) -> (A, B) {
+        let (a, b) = self.split_ref::<A, B>();
+        (*a, *b)
+    }
+
+    /// Split array reference into two array references.
+    ///
+    /// Length of `Self` MUST be equal to the sum of lengths of `A` and `B`.
+    /// This is checked at compile time, so this method never panics.
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    fn split_ref<A: Array<T>, B: Array<T>>(&self) -> (&A, &B) {
+        let () = LenCheck::<T, Self, A, B>::SUM_OK;
+        let ptr = self.as_ref().as_ptr();
+        // SAFETY: `Self` has the layout of `[T; A::LEN + B::LEN]`, while
+        // `A` and `B` have layouts of `[T; A::LEN]` and `[T; B::LEN]`
+        unsafe { (&*(ptr as *const A), &*(ptr.add(A::LEN) as *const B)) }
+    }
+
+    /// Split mutable array reference into two mutable array references.
+    ///
+    /// Length of `Self` MUST be equal to the sum of lengths of `A` and `B`.
+    /// This is checked at compile time, so this method never panics.
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    fn split_mut<A: Array<T>, B: Array<T>>(&mut self) -> (&mut A, &mut B) {
+        let () = LenCheck::<T, Self, A, B>::SUM_OK;
+        let ptr = self.as_mut().as_mut_ptr();
+        // SAFETY: `Self` has the layout of `[T; A::LEN + B::LEN]`, while
+        // `A` and `B` have layouts of `[T; A::LEN]` and `[T; B::LEN]`.
+        // The resulting references do not overlap.
+        unsafe { (&mut *(ptr as *mut A), &mut *(ptr.add(A::LEN) as *mut B)) }
+    }
+
+    /// Concatenate `self` with `other`.
+    ///
+    /// Length of `C` MUST be equal to the sum of lengths of `Self` and `B`.
+    /// This is checked at compile time, so this method never panics.
+    ///
+    /// ```
+    /// use array::Array;
+    ///
+    /// let nonce = [1u8, 2, 3];
+    /// let iv: [u8; 4] = nonce.concat([0u8]);
+    /// assert_eq!(iv, [1, 2, 3, 0]);
+    /// ```
+    #[inline]
+    fn concat<B: Array<T>, C: Array<T>>(self, other: B) -> C {
+        let mut res = C::ZERO;
+        let (a, b) = res.split_mut::<Self, B>();
+        *a = self;
+        *b = other;
+        res
+    }
 }
 
-impl<const N: usize> Array for [u8; N] {
+/// Compile-time length checks used by [`Array::split`] and friends.
+struct LenCheck<T, S, A, B>(PhantomData<(T, S, A, B)>);
+
+impl<T: Zero, S: Array<T>, A: Array<T>, B: Array<T>> LenCheck<T, S, A, B> {
+    const SUM_OK: () = assert!(
+        S::LEN == A::LEN + B::LEN,
+        "length of array must be equal to the sum of lengths of its parts",
+    );
+}
+
+// SAFETY: `[T; N]` trivially has the layout of `[T; N]`
+unsafe impl<T: Zero, const N: usize> Array<T> for [T; N] {
     const LEN: usize = N;
-    fn zero() -> Self {
-        [0; N]
-    }
 
-    fn try_from_slice(slice: &[u8]) -> Result<&Self, TryFromSliceError> {
+    #[inline]
+    fn try_from_slice(slice: &[T]) -> Result<&Self, TryFromSliceError> {
         <&Self>::try_from(slice)
     }
 
-    fn try_from_mut_slice(slice: &mut [u8]) -> Result<&mut Self, TryFromSliceError>{
+    #[inline]
+    fn try_from_mut_slice(slice: &mut [T]) -> Result<&mut Self, TryFromSliceError> {
         <&mut Self>::try_from(slice)
     }
 }
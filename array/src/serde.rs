@@ -0,0 +1,159 @@
+//! Serde support for [`Array`] types.
+//!
+//! Arrays are serialized as lower case hex strings in human-readable formats
+//! (e.g. JSON) and as byte strings in binary formats (e.g. bincode).
+//! Deserialization accepts only inputs of exactly [`Array::LEN`] bytes.
+//!
+//! Arrays can be (de)serialized using `#[serde(with = "array::serde")]`:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "array::serde")]
+//!     key: [u8; 4],
+//! }
+//!
+//! let cfg: Config = serde_json::from_str(r#"{"key":"deadbeef"}"#).unwrap();
+//! assert_eq!(cfg.key, [0xde, 0xad, 0xbe, 0xef]);
+//! assert_eq!(serde_json::to_string(&cfg).unwrap(), r#"{"key":"deadbeef"}"#);
+//! assert!(serde_json::from_str::<Config>(r#"{"key":"deadbe"}"#).is_err());
+//!
+//! let bin = bincode::serialize(&cfg).unwrap();
+//! assert_eq!(bin, [4, 0, 0, 0, 0, 0, 0, 0, 0xde, 0xad, 0xbe, 0xef]);
+//! assert!(bincode::deserialize::<Config>(&bin[..11]).is_err());
+//! ```
+//!
+//! The [`Hex`], [`Base64`] and [`Base64Url`] wrappers implement [`Serialize`]
+//! and [`Deserialize`] using their respective encoding in human-readable
+//! formats and raw bytes in binary formats.
+
+use crate::{Array, ArrayEncoding, Base64, Base64Url, DecodeError, Hex};
+use core::fmt;
+
+pub use ::serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serialize array as a hex string or as bytes depending on the format.
+#[inline]
+pub fn serialize<A: Array, S: Serializer>(arr: &A, serializer: S) -> Result<S::Ok, S::Error> {
+    Hex(arr).serialize(serializer)
+}
+
+/// Deserialize array from a hex string or from bytes depending on the format.
+#[inline]
+pub fn deserialize<'de, A: Array, D: Deserializer<'de>>(deserializer: D) -> Result<A, D::Error> {
+    Hex::<A>::deserialize(deserializer).map(|h| h.0)
+}
+
+impl<A: AsRef<[u8]>> Serialize for Hex<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(self.0.as_ref())
+        }
+    }
+}
+
+impl<'de, A: Array> Deserialize<'de> for Hex<A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ArrayVisitor::new("hex", A::from_hex)
+            .deserialize(deserializer)
+            .map(Self)
+    }
+}
+
+impl<A: AsRef<[u8]>> Serialize for Base64<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(self.0.as_ref())
+        }
+    }
+}
+
+impl<'de, A: Array> Deserialize<'de> for Base64<A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ArrayVisitor::new("Base64", A::from_base64)
+            .deserialize(deserializer)
+            .map(Self)
+    }
+}
+
+impl<A: AsRef<[u8]>> Serialize for Base64Url<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(self.0.as_ref())
+        }
+    }
+}
+
+impl<'de, A: Array> Deserialize<'de> for Base64Url<A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ArrayVisitor::new("URL-safe Base64", A::from_base64_url)
+            .deserialize(deserializer)
+            .map(Self)
+    }
+}
+
+/// Visitor which decodes array from a string using `decode`, or from
+/// bytes and sequences of exactly `A::LEN` elements.
+struct ArrayVisitor<A> {
+    encoding: &'static str,
+    decode: fn(&str) -> Result<A, DecodeError>,
+}
+
+impl<A: Array> ArrayVisitor<A> {
+    fn new(encoding: &'static str, decode: fn(&str) -> Result<A, DecodeError>) -> Self {
+        Self { encoding, decode }
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(self, deserializer: D) -> Result<A, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(self)
+        } else {
+            deserializer.deserialize_bytes(self)
+        }
+    }
+}
+
+impl<'de, A: Array> de::Visitor<'de> for ArrayVisitor<A> {
+    type Value = A;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes encoded as {} or a byte array",
+            A::LEN,
+            self.encoding
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<A, E> {
+        // the error intentionally does not include the input,
+        // since it may contain secret data
+        (self.decode)(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<A, E> {
+        A::try_copy_from_slice(v).map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_seq<S: de::SeqAccess<'de>>(self, mut seq: S) -> Result<A, S::Error> {
+        let mut res = A::zero();
+        for (i, dst) in res.as_mut().iter_mut().enumerate() {
+            *dst = match seq.next_element()? {
+                Some(v) => v,
+                None => return Err(de::Error::invalid_length(i, &self)),
+            };
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(A::LEN + 1, &self));
+        }
+        Ok(res)
+    }
+}
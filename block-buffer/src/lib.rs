@@ -7,46 +7,99 @@
 )]
 #![warn(missing_docs, rust_2018_idioms)]
 
-use core::{marker::PhantomData, slice};
+use core::{fmt, marker::PhantomData, slice};
 
 use array::Array;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
+#[cfg(feature = "zeroize")]
+pub use zeroize;
+
+mod read;
 mod sealed;
 
+use sealed::Cursor as _;
+
+pub use read::ReadBuffer;
+
 /// Trait for buffer kinds.
 pub trait BufferKind: sealed::Sealed {}
 
+/// Unsigned integer types used for storing cursor position of buffers:
+/// `u8`, `u16` and `u32`.
+pub trait Cursor: sealed::Cursor {}
+
 /// Eager block buffer kind, which guarantees that buffer position
 /// always lies in the range of `0..BlockSize`.
+///
+/// Position is stored as `C`, so blocks of up to `C::MAX + 1` bytes
+/// are supported, e.g. 256 bytes for `u8`.
 #[derive(Copy, Clone, Debug, Default)]
-pub struct Eager {}
+pub struct Eager<C = u8>(PhantomData<C>);
 
 /// Lazy block buffer kind, which guarantees that buffer position
 /// always lies in the range of `0..=BlockSize`.
+///
+/// Position is stored as `C`, so blocks of up to `C::MAX` bytes
+/// are supported, e.g. 255 bytes for `u8`.
 #[derive(Copy, Clone, Debug, Default)]
-pub struct Lazy {}
+pub struct Lazy<C = u8>(PhantomData<C>);
 
-impl BufferKind for Eager {}
-impl BufferKind for Lazy {}
+impl<C: Cursor> BufferKind for Eager<C> {}
+impl<C: Cursor> BufferKind for Lazy<C> {}
+
+/// Block buffer error.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Error;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("Block buffer error")
+    }
+}
 
 /// Eager block buffer.
-pub type EagerBuffer<B> = BlockBuffer<B, Eager>;
+pub type EagerBuffer<B, C = u8> = BlockBuffer<B, Eager<C>>;
 /// Lazy block buffer.
-pub type LazyBuffer<B> = BlockBuffer<B, Lazy>;
+pub type LazyBuffer<B, C = u8> = BlockBuffer<B, Lazy<C>>;
 
 /// Buffer for block processing of data.
+///
+/// Cursor position is stored using the cursor type of the buffer kind,
+/// [`u8`] by default. Blocks which do not fit it need a wider cursor,
+/// e.g. `Eager<u16>` for 512-byte blocks. Empty blocks and blocks too big
+/// for the cursor type result in a compilation error:
+///
+/// ```compile_fail
+/// use block_buffer::LazyBuffer;
+///
+/// // position 256 does not fit into `u8`
+/// let buf = LazyBuffer::<[u8; 256]>::default();
+/// ```
+///
+/// ```
+/// use block_buffer::LazyBuffer;
+///
+/// let buf = LazyBuffer::<[u8; 256], u16>::default();
+/// assert_eq!(core::mem::size_of_val(&buf), 258);
+/// ```
+///
+/// With enabled `zeroize` feature buffered data is wiped on reset and drop.
 #[derive(Debug)]
-pub struct BlockBuffer<Buffer, Kind: BufferKind> {
+pub struct BlockBuffer<Buffer: Array, Kind: BufferKind> {
     buffer: Buffer,
-    pos: u8,
+    pos: <Kind as sealed::Sealed>::Cursor,
     _pd: PhantomData<Kind>,
 }
 
 impl<Kind: BufferKind, Block: Array> Default for BlockBuffer<Block, Kind> {
+    #[allow(clippy::let_unit_value)]
     fn default() -> Self {
+        let () = Self::BLOCK_SIZE_CHECK;
         Self {
             buffer: Block::zero(),
-            pos: 0,
+            pos: Default::default(),
             _pd: PhantomData,
         }
     }
@@ -63,23 +116,56 @@ impl<Kind: BufferKind, Block: Array> Clone for BlockBuffer<Block, Kind> {
 }
 
 impl<Kind: BufferKind, Block: Array> BlockBuffer<Block, Kind> {
+    /// Neither buffer kind can hold data in zero-sized blocks and cursor
+    /// position must fit into the cursor type of the buffer kind.
+    const BLOCK_SIZE_CHECK: () = assert!(
+        Block::LEN != 0 && Block::LEN <= Kind::MAX_BLOCK_SIZE,
+        "block size must be non-zero and fit into the buffer cursor type",
+    );
+
     /// Create new buffer from slice.
     ///
     /// # Panics
     /// If slice length is not valid for used buffer kind.
     #[inline(always)]
+    #[allow(clippy::let_unit_value)]
     pub fn new(buf: &[u8]) -> Self {
+        let () = Self::BLOCK_SIZE_CHECK;
         let pos = buf.len();
         assert!(Kind::invariant(pos, Block::LEN));
         let mut buffer = Block::zero();
         buffer.as_mut()[..pos].copy_from_slice(buf);
         Self {
             buffer,
-            pos: pos as u8,
+            pos: sealed::Cursor::from_usize(pos),
             _pd: PhantomData,
         }
     }
 
+    /// Create new buffer from slice.
+    ///
+    /// Returns an error if slice length is not valid for used buffer kind.
+    #[inline(always)]
+    #[allow(clippy::let_unit_value)]
+    pub fn try_new(buf: &[u8]) -> Result<Self, Error> {
+        let () = Self::BLOCK_SIZE_CHECK;
+        if Kind::invariant(buf.len(), Block::LEN) {
+            Ok(Self::new(buf))
+        } else {
+            Err(Error)
+        }
+    }
+
+    /// Split `data` into blocks which [`digest_blocks`][Self::digest_blocks]
+    /// passes to the compression function when called on an empty buffer,
+    /// and the tail which it stores in the buffer.
+    ///
+    /// The tail can be used to create buffer using [`new`][Self::new].
+    #[inline(always)]
+    pub fn split_blocks(data: &[u8]) -> (&[Block], &[u8]) {
+        Kind::split_blocks(data)
+    }
+
     /// Digest data in `input` in blocks of size `BlockSize` using
     /// the `compress` function, which accepts slice of blocks.
     #[inline]
@@ -122,6 +208,8 @@ impl<Kind: BufferKind, Block: Array> BlockBuffer<Block, Kind> {
     /// Reset buffer by setting cursor position to zero.
     #[inline(always)]
     pub fn reset(&mut self) {
+        #[cfg(feature = "zeroize")]
+        self.buffer.as_mut().zeroize();
         self.set_pos_unchecked(0);
     }
 
@@ -134,10 +222,22 @@ impl<Kind: BufferKind, Block: Array> BlockBuffer<Block, Kind> {
         &mut self.buffer
     }
 
+    /// Pad remaining data with zeros and compress the resulting block.
+    ///
+    /// In addition to the block `compress` receives the number of data bytes
+    /// in it, as needed by BLAKE-style finalization. The block is compressed
+    /// even if the buffer is empty.
+    #[inline]
+    pub fn zero_padding(&mut self, compress: impl FnOnce(&Block, usize)) {
+        let pos = self.get_pos();
+        let block = self.pad_with_zeros();
+        compress(block, pos);
+    }
+
     /// Return current cursor position.
     #[inline(always)]
     pub fn get_pos(&self) -> usize {
-        let pos = self.pos as usize;
+        let pos = self.pos.to_usize();
         if !Kind::invariant(pos, Block::LEN) {
             debug_assert!(false);
             // SAFETY: `pos` never breaks the invariant
@@ -165,6 +265,26 @@ impl<Kind: BufferKind, Block: Array> BlockBuffer<Block, Kind> {
         self.set_pos_unchecked(pos);
     }
 
+    /// Set buffer content and cursor position.
+    ///
+    /// Returns an error if `pos` is not valid for used buffer kind.
+    #[inline]
+    pub fn try_set(&mut self, buf: Block, pos: usize) -> Result<(), Error> {
+        if Kind::invariant(pos, Block::LEN) {
+            self.set(buf, pos);
+            Ok(())
+        } else {
+            Err(Error)
+        }
+    }
+
+    /// Return reference to the whole internal block, including
+    /// bytes after the cursor position.
+    #[inline(always)]
+    pub fn get_block(&self) -> &Block {
+        &self.buffer
+    }
+
     /// Return size of the internall buffer in bytes.
     #[inline(always)]
     pub fn size(&self) -> usize {
@@ -180,11 +300,32 @@ impl<Kind: BufferKind, Block: Array> BlockBuffer<Block, Kind> {
     #[inline(always)]
     fn set_pos_unchecked(&mut self, pos: usize) {
         debug_assert!(Kind::invariant(pos, Block::LEN));
-        self.pos = pos as u8;
+        // `pos` fits into the cursor type because of `BLOCK_SIZE_CHECK`
+        self.pos = sealed::Cursor::from_usize(pos);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<Kind: BufferKind, Block: Array> Zeroize for BlockBuffer<Block, Kind> {
+    #[inline]
+    fn zeroize(&mut self) {
+        self.buffer.as_mut().zeroize();
+        self.pos.wipe();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<Kind: BufferKind, Block: Array> Drop for BlockBuffer<Block, Kind> {
+    #[inline]
+    fn drop(&mut self) {
+        self.zeroize();
     }
 }
 
-impl<Block: Array> BlockBuffer<Block, Eager> {
+#[cfg(feature = "zeroize")]
+impl<Kind: BufferKind, Block: Array> ZeroizeOnDrop for BlockBuffer<Block, Kind> {}
+
+impl<Block: Array, C: Cursor> BlockBuffer<Block, Eager<C>> {
     /// Set `data` to generated blocks.
     #[inline]
     pub fn set_data(
@@ -207,7 +348,7 @@ impl<Block: Array> BlockBuffer<Block, Eager> {
             left.copy_from_slice(&self.buffer.as_ref()[pos..]);
         }
 
-        let (blocks, leftover) = to_blocks_mut(data);
+        let (blocks, leftover) = Block::slice_as_chunks_mut(data);
         process_blocks(blocks);
 
         let n = leftover.len();
@@ -221,31 +362,41 @@ impl<Block: Array> BlockBuffer<Block, Eager> {
     }
 
     /// Compress remaining data after padding it with `delim`, zeros and
-    /// the `suffix` bytes. If there is not enough unused space, `compress`
-    /// will be called twice.
+    /// the `suffix` bytes. The padding uses as many blocks as needed to fit
+    /// the suffix, i.e. `compress` is called once or more times.
     ///
-    /// # Panics
-    /// If suffix length is bigger than block size.
+    /// ```
+    /// use block_buffer::EagerBuffer;
+    ///
+    /// let mut buf = EagerBuffer::<[u8; 4]>::new(&[1, 2]);
+    /// let mut blocks = Vec::new();
+    /// buf.digest_pad(0x80, &[3, 4, 5, 6], |b| blocks.push(*b));
+    /// assert_eq!(blocks, [[1, 2, 0x80, 0], [3, 4, 5, 6]]);
+    /// assert_eq!(buf.get_pos(), 0);
+    /// ```
     #[inline(always)]
     pub fn digest_pad(&mut self, delim: u8, suffix: &[u8], mut compress: impl FnMut(&Block)) {
-        if suffix.len() > Block::LEN {
-            panic!("suffix is too long");
-        }
+        let bs = Block::LEN;
         let pos = self.get_pos();
-        self.buffer.as_mut()[pos] = delim;
-        for b in &mut self.buffer.as_mut()[pos + 1..] {
-            *b = 0;
-        }
-
-        let n = self.size() - suffix.len();
-        if self.size() - pos - 1 < suffix.len() {
-            compress(&self.buffer);
-            let mut block = Block::zero();
-            block.as_mut()[n..].copy_from_slice(suffix);
+        let buf = self.buffer.as_mut();
+        buf[pos] = delim;
+        buf[pos + 1..].iter_mut().for_each(|b| *b = 0);
+
+        // length of the padded data starting from the buffer beginning
+        let total = (pos + 1 + suffix.len() + bs - 1) / bs * bs;
+        let suffix_pos = total - suffix.len();
+        let mut block = self.buffer;
+        let mut offset = 0;
+        while offset < total {
+            let start = core::cmp::max(suffix_pos, offset);
+            let end = offset + bs;
+            if start < end {
+                let part = &suffix[start - suffix_pos..end - suffix_pos];
+                block.as_mut()[start - offset..].copy_from_slice(part);
+            }
             compress(&block);
-        } else {
-            self.buffer.as_mut()[n..].copy_from_slice(suffix);
-            compress(&self.buffer);
+            block = Block::zero();
+            offset = end;
         }
         self.set_pos_unchecked(0)
     }
@@ -270,16 +421,27 @@ impl<Block: Array> BlockBuffer<Block, Eager> {
     pub fn len128_padding_be(&mut self, data_len: u128, compress: impl FnMut(&Block)) {
         self.digest_pad(0x80, &data_len.to_be_bytes(), compress);
     }
-}
 
-/// Split message into mutable slice of parallel blocks, blocks, and leftover bytes.
-#[inline(always)]
-fn to_blocks_mut<Block: Array>(data: &mut [u8]) -> (&mut [Block], &mut [u8]) {
-    let nb = data.len() / Block::LEN;
-    let (left, right) = data.split_at_mut(nb * Block::LEN);
-    let p = left.as_mut_ptr() as *mut Block;
-    // SAFETY: we guarantee that `blocks` does not point outside of `data`, and `p` is valid for
-    // mutation
-    let blocks = unsafe { slice::from_raw_parts_mut(p, nb) };
-    (blocks, right)
+    /// Pad message with 0x80, zeros and 128-bit message length using
+    /// little-endian byte order.
+    #[inline]
+    pub fn len128_padding_le(&mut self, data_len: u128, compress: impl FnMut(&Block)) {
+        self.digest_pad(0x80, &data_len.to_le_bytes(), compress);
+    }
+
+    /// Pad message using the Keccak `pad10*1` rule: the `domain` byte, zeros
+    /// and the final bit set in the last byte of the block.
+    ///
+    /// `domain` contains the domain separation bits followed by the first
+    /// padding bit, e.g. `0x06` for SHA-3 and `0x1F` for SHAKE.
+    #[inline]
+    pub fn keccak_padding(&mut self, domain: u8, compress: impl FnOnce(&Block)) {
+        let pos = self.get_pos();
+        let buf = self.buffer.as_mut();
+        buf[pos] = domain;
+        buf[pos + 1..].iter_mut().for_each(|b| *b = 0);
+        buf[Block::LEN - 1] |= 0x80;
+        compress(&self.buffer);
+        self.set_pos_unchecked(0);
+    }
 }
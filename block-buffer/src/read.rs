@@ -0,0 +1,205 @@
+use super::{Cursor, Error};
+use array::Array;
+use core::fmt;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Buffer for reading block-generated data, e.g. XOF output or keystream.
+///
+/// The buffer caches the last generated block and position of the read
+/// cursor inside it. Bytes after the cursor are not yet read. Cursor position
+/// always lies in the range of `0..BlockSize`, position equal to zero means
+/// that the buffer does not contain unread bytes.
+///
+/// Cursor position is stored as `C`, [`u8`] by default, so blocks of up to
+/// `C::MAX + 1` bytes are supported, e.g. 256 bytes for `u8`. Empty blocks
+/// and blocks too big for the cursor type result in a compilation error:
+///
+/// ```compile_fail
+/// use block_buffer::ReadBuffer;
+///
+/// // position 256 does not fit into `u8`
+/// let buf = ReadBuffer::<[u8; 257]>::default();
+/// ```
+///
+/// ```
+/// use block_buffer::ReadBuffer;
+///
+/// let buf = ReadBuffer::<[u8; 512], u16>::default();
+/// assert_eq!(core::mem::size_of_val(&buf), 514);
+/// ```
+///
+/// With enabled `zeroize` feature cached data is wiped on reset and drop.
+pub struct ReadBuffer<Block: Array, C: Cursor = u8> {
+    buffer: Block,
+    pos: C,
+}
+
+impl<Block: Array, C: Cursor> fmt::Debug for ReadBuffer<Block, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadBuffer")
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+impl<Block: Array, C: Cursor> Default for ReadBuffer<Block, C> {
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    fn default() -> Self {
+        let () = Self::BLOCK_SIZE_CHECK;
+        Self {
+            buffer: Block::zero(),
+            pos: Default::default(),
+        }
+    }
+}
+
+impl<Block: Array, C: Cursor> Clone for ReadBuffer<Block, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer,
+            pos: self.pos,
+        }
+    }
+}
+
+impl<Block: Array, C: Cursor> ReadBuffer<Block, C> {
+    /// Cursor position is always smaller than block size.
+    const BLOCK_SIZE_CHECK: () = assert!(
+        Block::LEN != 0 && Block::LEN <= C::MAX.saturating_add(1),
+        "block size must be non-zero and fit into the buffer cursor type",
+    );
+
+    /// Return current cursor position.
+    #[inline(always)]
+    pub fn get_pos(&self) -> usize {
+        let pos = self.pos.to_usize();
+        if pos >= Block::LEN {
+            debug_assert!(false);
+            // SAFETY: `pos` is always smaller than block size
+            unsafe { core::hint::unreachable_unchecked() }
+        }
+        pos
+    }
+
+    /// Return size of the internal buffer in bytes.
+    #[inline(always)]
+    pub fn size(&self) -> usize {
+        Block::LEN
+    }
+
+    /// Return number of unread bytes in the internal buffer.
+    #[inline(always)]
+    pub fn remaining(&self) -> usize {
+        match self.get_pos() {
+            0 => 0,
+            pos => Block::LEN - pos,
+        }
+    }
+
+    /// Return reference to the whole cached block, including
+    /// already read bytes before the cursor position.
+    #[inline(always)]
+    pub fn get_block(&self) -> &Block {
+        &self.buffer
+    }
+
+    /// Set cached block and cursor position.
+    ///
+    /// Returns an error if `pos` is bigger or equal to block size.
+    #[inline]
+    pub fn try_set(&mut self, buf: Block, pos: usize) -> Result<(), Error> {
+        if pos < Block::LEN {
+            self.buffer = buf;
+            self.pos = C::from_usize(pos);
+            Ok(())
+        } else {
+            Err(Error)
+        }
+    }
+
+    /// Discard unread bytes.
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        #[cfg(feature = "zeroize")]
+        self.buffer.as_mut().zeroize();
+        self.pos = C::from_usize(0);
+    }
+
+    /// Read up to `len` unread bytes and advance the cursor past them.
+    ///
+    /// Returns an empty slice if the buffer does not contain unread bytes.
+    #[inline]
+    pub fn read_cached(&mut self, len: usize) -> &[u8] {
+        let pos = self.get_pos();
+        if pos == 0 {
+            return &[];
+        }
+        let n = core::cmp::min(len, Block::LEN - pos);
+        let new_pos = pos + n;
+        self.pos = C::from_usize(if new_pos == Block::LEN { 0 } else { new_pos });
+        &self.buffer.as_ref()[pos..new_pos]
+    }
+
+    /// Generate new block using `gen_block`, pass its first `pos` bytes
+    /// to `read_fn` and cache the rest.
+    ///
+    /// Unread bytes in the buffer are discarded.
+    ///
+    /// # Panics
+    /// If `pos` is bigger or equal to block size.
+    #[inline]
+    pub fn write_block(
+        &mut self,
+        pos: usize,
+        gen_block: impl FnOnce(&mut Block),
+        read_fn: impl FnOnce(&[u8]),
+    ) {
+        assert!(pos < Block::LEN);
+        gen_block(&mut self.buffer);
+        read_fn(&self.buffer.as_ref()[..pos]);
+        self.pos = C::from_usize(pos);
+    }
+
+    /// Fill `data` with unread bytes followed by bytes of blocks
+    /// generated by `gen_block`.
+    ///
+    /// Blocks which fully fit into `data` are generated in place. Unread
+    /// part of the last generated block is cached for the following reads.
+    #[inline]
+    pub fn read(&mut self, data: &mut [u8], mut gen_block: impl FnMut(&mut Block)) {
+        let cached = self.read_cached(data.len());
+        let (head, data) = data.split_at_mut(cached.len());
+        head.copy_from_slice(cached);
+
+        let (blocks, tail) = Block::slice_as_chunks_mut(data);
+        blocks.iter_mut().for_each(&mut gen_block);
+
+        let n = tail.len();
+        if n != 0 {
+            self.write_block(n, gen_block, |block| tail.copy_from_slice(block));
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<Block: Array, C: Cursor> Zeroize for ReadBuffer<Block, C> {
+    #[inline]
+    fn zeroize(&mut self) {
+        self.buffer.as_mut().zeroize();
+        self.pos.wipe();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<Block: Array, C: Cursor> Drop for ReadBuffer<Block, C> {
+    #[inline]
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<Block: Array, C: Cursor> ZeroizeOnDrop for ReadBuffer<Block, C> {}
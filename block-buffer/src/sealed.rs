@@ -1,9 +1,66 @@
-use core::slice;
-
+use super::{Eager, Lazy};
 use array::Array;
+use core::fmt;
+
+/// Sealed trait for cursor types.
+pub trait Cursor: Copy + Default + fmt::Debug + Eq {
+    /// Maximum cursor position which can be stored.
+    const MAX: usize;
+
+    /// Convert position to cursor, `pos` must not be bigger than `MAX`.
+    fn from_usize(pos: usize) -> Self;
+
+    /// Convert cursor to position.
+    fn to_usize(self) -> usize;
+
+    /// Wipe cursor value.
+    #[cfg(feature = "zeroize")]
+    fn wipe(&mut self);
+}
+
+macro_rules! impl_cursor {
+    ($($t:ty)*) => {
+        $(
+            impl Cursor for $t {
+                const MAX: usize = if (<$t>::MAX as u128) < (usize::MAX as u128) {
+                    <$t>::MAX as usize
+                } else {
+                    usize::MAX
+                };
+
+                #[inline(always)]
+                fn from_usize(pos: usize) -> Self {
+                    debug_assert!(pos <= <Self as Cursor>::MAX);
+                    pos as $t
+                }
+
+                #[inline(always)]
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+
+                #[cfg(feature = "zeroize")]
+                #[inline(always)]
+                fn wipe(&mut self) {
+                    zeroize::Zeroize::zeroize(self);
+                }
+            }
+
+            impl super::Cursor for $t {}
+        )*
+    };
+}
+
+impl_cursor! { u8 u16 u32 }
 
 /// Sealed trait for buffer kinds.
 pub trait Sealed {
+    /// Type used for storing cursor position.
+    type Cursor: super::Cursor;
+
+    /// Maximum block size for which cursor position fits into `Cursor`.
+    const MAX_BLOCK_SIZE: usize;
+
     /// Invariant guaranteed by a buffer kind, i.e. with correct
     /// buffer code this function always returns true.
     fn invariant(pos: usize, block_size: usize) -> bool;
@@ -12,7 +69,11 @@ pub trait Sealed {
     fn split_blocks<Block: Array>(data: &[u8]) -> (&[Block], &[u8]);
 }
 
-impl Sealed for super::Eager {
+impl<C: super::Cursor> Sealed for Eager<C> {
+    type Cursor = C;
+    // position is always smaller than block size
+    const MAX_BLOCK_SIZE: usize = C::MAX.saturating_add(1);
+
     #[inline(always)]
     fn invariant(pos: usize, block_size: usize) -> bool {
         pos < block_size
@@ -20,23 +81,15 @@ impl Sealed for super::Eager {
 
     #[inline(always)]
     fn split_blocks<Block: Array>(data: &[u8]) -> (&[Block], &[u8]) {
-        let nb = data.len() / Block::LEN;
-        let blocks_len = nb * Block::LEN;
-        let tail_len = data.len() - blocks_len;
-        // SAFETY: we guarantee that created slices do not point
-        // outside of `data`
-        unsafe {
-            let blocks_ptr = data.as_ptr() as *const Block;
-            let tail_ptr = data.as_ptr().add(blocks_len);
-            (
-                slice::from_raw_parts(blocks_ptr, nb),
-                slice::from_raw_parts(tail_ptr, tail_len),
-            )
-        }
+        Block::slice_as_chunks(data)
     }
 }
 
-impl Sealed for super::Lazy {
+impl<C: super::Cursor> Sealed for Lazy<C> {
+    type Cursor = C;
+    // position can be equal to block size
+    const MAX_BLOCK_SIZE: usize = C::MAX;
+
     #[inline(always)]
     fn invariant(pos: usize, block_size: usize) -> bool {
         pos <= block_size
@@ -47,22 +100,14 @@ impl Sealed for super::Lazy {
         if data.is_empty() {
             return (&[], &[]);
         }
-        let (nb, tail_len) = if data.len() % Block::LEN == 0 {
-            (data.len() / Block::LEN - 1, Block::LEN)
+        // the last block is always left in the tail, even if it's full
+        let nb = if data.len() % Block::LEN == 0 {
+            data.len() / Block::LEN - 1
         } else {
-            let nb = data.len() / Block::LEN;
-            (nb, data.len() - nb * Block::LEN)
+            data.len() / Block::LEN
         };
-        let blocks_len = nb * Block::LEN;
-        // SAFETY: we guarantee that created slices do not point
-        // outside of `data`
-        unsafe {
-            let blocks_ptr = data.as_ptr() as *const Block;
-            let tail_ptr = data.as_ptr().add(blocks_len);
-            (
-                slice::from_raw_parts(blocks_ptr, nb),
-                slice::from_raw_parts(tail_ptr, tail_len),
-            )
-        }
+        let (blocks, tail) = data.split_at(nb * Block::LEN);
+        let (blocks, _) = Block::slice_as_chunks(blocks);
+        (blocks, tail)
     }
 }
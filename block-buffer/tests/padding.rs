@@ -0,0 +1,201 @@
+//! Padding routines at the edges of the buffer.
+use array::Array;
+use block_buffer::{BlockBuffer, Cursor, Eager, EagerBuffer, Lazy, LazyBuffer};
+
+fn data(len: usize) -> Vec<u8> {
+    (1..=len).map(|i| (i % 251) as u8).collect()
+}
+
+fn collect<B: Array>(f: impl FnOnce(&mut dyn FnMut(&B))) -> Vec<u8> {
+    let mut out = Vec::new();
+    f(&mut |b: &B| out.extend_from_slice(b.as_ref()));
+    out
+}
+
+/// Reference padding: data, `delim`, the minimal number of zeros
+/// and `suffix`, aligned to the block size.
+fn pad(bs: usize, data: &[u8], delim: u8, suffix: &[u8]) -> Vec<u8> {
+    let mut res = data.to_vec();
+    res.push(delim);
+    while (res.len() + suffix.len()) % bs != 0 {
+        res.push(0);
+    }
+    res.extend_from_slice(suffix);
+    res
+}
+
+fn check_digest_pad<B: Array, C: Cursor>() {
+    let bs = B::LEN;
+    let suffix = data(2 * bs + 20);
+    for &len in &[0, 1, bs / 2, bs - 1] {
+        if len >= bs {
+            continue;
+        }
+        for &slen in &[0, 1, bs - len - 1, bs - len, bs, bs + 1, 2 * bs + 20] {
+            let msg = data(len);
+            let suffix = &suffix[..slen];
+            let mut buf = EagerBuffer::<B, C>::new(&msg);
+            let out = collect::<B>(|f| buf.digest_pad(0x80, suffix, f));
+            let expected = pad(bs, &msg, 0x80, suffix);
+            assert_eq!(out, expected, "bs {} len {} suffix {}", bs, len, slen);
+            assert_eq!(buf.get_pos(), 0);
+        }
+    }
+}
+
+#[test]
+fn digest_pad() {
+    check_digest_pad::<[u8; 1], u8>();
+    check_digest_pad::<[u8; 4], u8>();
+    check_digest_pad::<[u8; 64], u8>();
+    check_digest_pad::<[u8; 256], u8>();
+    check_digest_pad::<[u8; 257], u16>();
+    check_digest_pad::<[u8; 512], u16>();
+}
+
+#[test]
+fn digest_pad_blocks() {
+    // empty buffer with the suffix fitting into the first block
+    let mut buf = EagerBuffer::<[u8; 4]>::default();
+    let out = collect(|f| buf.digest_pad(0x80, &[7, 8], f));
+    assert_eq!(out, [0x80, 0, 7, 8]);
+
+    // one byte short of full, the suffix goes into the second block
+    let mut buf = EagerBuffer::<[u8; 4]>::new(&[1, 2, 3]);
+    let out = collect(|f| buf.digest_pad(0x80, &[7, 8], f));
+    assert_eq!(out, [1, 2, 3, 0x80, 0, 0, 7, 8]);
+
+    // suffix spilling over the block boundary
+    let mut buf = EagerBuffer::<[u8; 4]>::new(&[1, 2]);
+    let out = collect(|f| buf.digest_pad(0x80, &[5, 6, 7, 8, 9], f));
+    assert_eq!(out, [1, 2, 0x80, 5, 6, 7, 8, 9]);
+    let mut buf = EagerBuffer::<[u8; 4]>::new(&[1, 2]);
+    let out = collect(|f| buf.digest_pad(0x80, &[5, 6, 7, 8, 9, 10, 11], f));
+    assert_eq!(out, [1, 2, 0x80, 0, 0, 5, 6, 7, 8, 9, 10, 11]);
+
+    // one-byte blocks
+    let mut buf = EagerBuffer::<[u8; 1]>::default();
+    let out = collect(|f| buf.digest_pad(0x80, &[7, 8], f));
+    assert_eq!(out, [0x80, 7, 8]);
+}
+
+fn check_len128_le<B: Array, C: Cursor>() {
+    let bs = B::LEN;
+    let data_len = 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128;
+    for &len in &[0, 1, bs / 2, bs - 1] {
+        if len >= bs {
+            continue;
+        }
+        let msg = data(len);
+        let mut buf = EagerBuffer::<B, C>::new(&msg);
+        let out = collect::<B>(|f| buf.len128_padding_le(data_len, f));
+        let expected = pad(bs, &msg, 0x80, &data_len.to_le_bytes());
+        assert_eq!(out, expected, "bs {} len {}", bs, len);
+        assert_eq!(buf.get_pos(), 0);
+    }
+}
+
+#[test]
+fn len128_padding_le() {
+    check_len128_le::<[u8; 1], u8>();
+    check_len128_le::<[u8; 16], u8>();
+    check_len128_le::<[u8; 17], u8>();
+    check_len128_le::<[u8; 128], u8>();
+    check_len128_le::<[u8; 256], u8>();
+    check_len128_le::<[u8; 512], u16>();
+
+    // 128-byte blocks with the length in the same and in the next block
+    let mut buf = EagerBuffer::<[u8; 128]>::new(&[0xAA; 111]);
+    let out = collect(|f| buf.len128_padding_le(888, f));
+    assert_eq!(out.len(), 128);
+    assert_eq!(out[111], 0x80);
+    assert_eq!(out[112..], 888u128.to_le_bytes());
+
+    let mut buf = EagerBuffer::<[u8; 128]>::new(&[0xAA; 112]);
+    let out = collect(|f| buf.len128_padding_le(896, f));
+    assert_eq!(out.len(), 256);
+    assert_eq!(out[112], 0x80);
+    assert!(out[113..240].iter().all(|&b| b == 0));
+    assert_eq!(out[240..], 896u128.to_le_bytes());
+}
+
+fn check_keccak<B: Array, C: Cursor>() {
+    let bs = B::LEN;
+    for &len in &[0, 1, bs / 2, bs - 1] {
+        if len >= bs {
+            continue;
+        }
+        let msg = data(len);
+        let mut buf = EagerBuffer::<B, C>::new(&msg);
+        let out = collect::<B>(|f| buf.keccak_padding(0x06, |b| f(b)));
+        let mut expected = msg.clone();
+        expected.push(0x06);
+        expected.resize(bs, 0);
+        expected[bs - 1] |= 0x80;
+        assert_eq!(out, expected, "bs {} len {}", bs, len);
+        assert_eq!(buf.get_pos(), 0);
+    }
+}
+
+#[test]
+fn keccak_padding() {
+    check_keccak::<[u8; 1], u8>();
+    check_keccak::<[u8; 2], u8>();
+    check_keccak::<[u8; 136], u8>();
+    check_keccak::<[u8; 168], u8>();
+    check_keccak::<[u8; 256], u8>();
+    check_keccak::<[u8; 512], u16>();
+
+    // domain byte and the final bit share the last byte
+    let mut buf = EagerBuffer::<[u8; 4]>::new(&[1, 2, 3]);
+    let out = collect(|f| buf.keccak_padding(0x1F, |b| f(b)));
+    assert_eq!(out, [1, 2, 3, 0x9F]);
+    let mut buf = EagerBuffer::<[u8; 4]>::default();
+    let out = collect(|f| buf.keccak_padding(0x1F, |b| f(b)));
+    assert_eq!(out, [0x1F, 0, 0, 0x80]);
+}
+
+/// Check `zero_padding` for every buffer length accepted by the kind.
+fn check_zero_padding<B: Array, K>(lens: impl Iterator<Item = usize>)
+where
+    K: block_buffer::BufferKind,
+{
+    let bs = B::LEN;
+    for len in lens {
+        let msg = data(len);
+        let mut buf = BlockBuffer::<B, K>::new(&msg);
+        // stale bytes past the cursor are overwritten by zeros
+        let mut stale = *B::try_from_slice(&data(bs)).unwrap();
+        stale.as_mut()[..len].copy_from_slice(&msg);
+        buf.set(stale, len);
+
+        let mut calls = 0;
+        buf.zero_padding(|block, n| {
+            calls += 1;
+            assert_eq!(n, len, "bs {}", bs);
+            assert_eq!(block.as_ref()[..len], msg[..]);
+            assert!(block.as_ref()[len..].iter().all(|&b| b == 0));
+        });
+        assert_eq!(calls, 1);
+        assert_eq!(buf.get_pos(), 0);
+    }
+}
+
+#[test]
+fn zero_padding() {
+    check_zero_padding::<[u8; 1], Eager>(0..1);
+    check_zero_padding::<[u8; 8], Eager>(0..8);
+    check_zero_padding::<[u8; 256], Eager>([0, 1, 255].iter().copied());
+    check_zero_padding::<[u8; 512], Eager<u16>>([0, 1, 511].iter().copied());
+
+    // lazy buffers can hold a full block
+    check_zero_padding::<[u8; 1], Lazy>(0..=1);
+    check_zero_padding::<[u8; 8], Lazy>(0..=8);
+    check_zero_padding::<[u8; 255], Lazy>([0, 1, 254, 255].iter().copied());
+    check_zero_padding::<[u8; 512], Lazy<u16>>([0, 1, 511, 512].iter().copied());
+
+    let mut buf = LazyBuffer::<[u8; 4]>::new(&[1, 2, 3, 4]);
+    buf.zero_padding(|block, n| assert_eq!((block, n), (&[1, 2, 3, 4], 4)));
+    let mut buf = EagerBuffer::<[u8; 4]>::new(&[1]);
+    buf.zero_padding(|block, n| assert_eq!((block, n), (&[1, 0, 0, 0], 1)));
+}
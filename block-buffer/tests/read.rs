@@ -0,0 +1,181 @@
+//! Reading block-generated data through `ReadBuffer`.
+use array::Array;
+use block_buffer::{Cursor, Error, ReadBuffer};
+use core::mem::size_of;
+
+/// Block generator producing the byte sequence `0, 1, 2, ...` (mod 251).
+struct Gen {
+    pos: usize,
+}
+
+impl Gen {
+    fn new() -> Self {
+        Self { pos: 0 }
+    }
+
+    fn block<B: Array>(&mut self, block: &mut B) {
+        for b in block.as_mut().iter_mut() {
+            *b = (self.pos % 251) as u8;
+            self.pos += 1;
+        }
+    }
+}
+
+fn data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn sizes() {
+    assert_eq!(size_of::<ReadBuffer<[u8; 64]>>(), 65);
+    assert_eq!(size_of::<ReadBuffer<[u8; 256]>>(), 257);
+    assert_eq!(size_of::<ReadBuffer<[u8; 257], u16>>(), 260);
+    assert_eq!(size_of::<ReadBuffer<[u8; 512], u16>>(), 514);
+}
+
+/// Read `len` bytes in chunks of `chunk` bytes.
+fn check_read<B: Array, C: Cursor>(len: usize, chunk: usize) {
+    let bs = B::LEN;
+    let mut buf = ReadBuffer::<B, C>::default();
+    let mut gen = Gen::new();
+    let mut out = vec![0u8; len];
+    for c in out.chunks_mut(chunk) {
+        buf.read(c, |b| gen.block(b));
+    }
+    assert_eq!(out, data(len), "len {} chunk {}", len, chunk);
+    // only whole blocks are generated
+    let blocks = (len + bs - 1) / bs;
+    assert_eq!(gen.pos, blocks * bs);
+    assert_eq!(buf.remaining(), blocks * bs - len);
+    assert_eq!(buf.get_pos(), len % bs);
+}
+
+fn check<B: Array, C: Cursor>() {
+    let bs = B::LEN;
+    for &len in &[0, 1, bs - 1, bs, bs + 1, 3 * bs - 1, 3 * bs, 3 * bs + 7] {
+        for &chunk in &[1, 7, bs - 1, bs, bs + 1, 4 * bs] {
+            if chunk != 0 {
+                check_read::<B, C>(len, chunk);
+            }
+        }
+    }
+}
+
+#[test]
+fn read() {
+    check::<[u8; 1], u8>();
+    check::<[u8; 8], u8>();
+    check::<[u8; 64], u8>();
+    check::<[u8; 256], u8>();
+    check::<[u8; 257], u16>();
+    check::<[u8; 512], u16>();
+    check::<[u8; 512], u32>();
+}
+
+#[test]
+fn read_cached() {
+    let mut buf = ReadBuffer::<[u8; 8]>::default();
+    assert_eq!(buf.size(), 8);
+    assert_eq!(buf.get_pos(), 0);
+    assert_eq!(buf.remaining(), 0);
+    // empty buffer does not contain unread bytes
+    assert_eq!(buf.read_cached(5), []);
+
+    let block = [0, 1, 2, 3, 4, 5, 6, 7];
+    buf.try_set(block, 3).unwrap();
+    assert_eq!(buf.remaining(), 5);
+    assert_eq!(buf.read_cached(0), []);
+    assert_eq!(buf.read_cached(2), [3, 4]);
+    assert_eq!(buf.get_pos(), 5);
+    // reads are limited by the number of unread bytes
+    assert_eq!(buf.read_cached(10), [5, 6, 7]);
+    assert_eq!(buf.get_pos(), 0);
+    assert_eq!(buf.remaining(), 0);
+    assert_eq!(buf.read_cached(10), []);
+
+    buf.try_set(block, 7).unwrap();
+    assert_eq!(buf.read_cached(1), [7]);
+    assert_eq!(buf.get_pos(), 0);
+}
+
+#[test]
+fn write_block() {
+    let mut buf = ReadBuffer::<[u8; 8]>::default();
+    let mut read = Vec::new();
+    buf.write_block(
+        3,
+        |b| *b = [10, 11, 12, 13, 14, 15, 16, 17],
+        |r| read.extend_from_slice(r),
+    );
+    assert_eq!(read, [10, 11, 12]);
+    assert_eq!(buf.get_pos(), 3);
+    assert_eq!(buf.remaining(), 5);
+    assert_eq!(buf.read_cached(8), [13, 14, 15, 16, 17]);
+
+    // unread bytes are discarded
+    buf.try_set([0; 8], 1).unwrap();
+    buf.write_block(0, |b| *b = [1; 8], |r| assert!(r.is_empty()));
+    assert_eq!(buf.get_pos(), 0);
+    assert_eq!(buf.remaining(), 0);
+    assert_eq!(buf.get_block(), &[1; 8]);
+}
+
+#[test]
+#[should_panic]
+fn write_block_full() {
+    let mut buf = ReadBuffer::<[u8; 8]>::default();
+    buf.write_block(8, |_| {}, |_| {});
+}
+
+#[test]
+fn try_set() {
+    let block = [0, 1, 2, 3, 4, 5, 6, 7];
+    let mut buf = ReadBuffer::<[u8; 8]>::default();
+    assert_eq!(buf.try_set(block, 0), Ok(()));
+    assert_eq!(buf.get_block(), &block);
+    assert_eq!(buf.remaining(), 0);
+    assert_eq!(buf.try_set(block, 7), Ok(()));
+    assert_eq!(buf.remaining(), 1);
+
+    // position equal to or bigger than block size is rejected
+    // and the buffer is left unchanged
+    for &pos in &[8, 9, 256, usize::MAX] {
+        assert_eq!(buf.try_set([9; 8], pos), Err(Error));
+        assert_eq!(buf.get_pos(), 7);
+        assert_eq!(buf.get_block(), &block);
+    }
+
+    let mut buf = ReadBuffer::<[u8; 512], u16>::default();
+    assert_eq!(buf.try_set([1; 512], 511), Ok(()));
+    assert_eq!(buf.get_pos(), 511);
+    assert_eq!(buf.try_set([1; 512], 512), Err(Error));
+    assert_eq!(buf.try_set([1; 512], 1 << 16), Err(Error));
+    assert_eq!(buf.get_pos(), 511);
+}
+
+#[test]
+fn reset() {
+    let mut buf = ReadBuffer::<[u8; 8]>::default();
+    buf.try_set([1; 8], 2).unwrap();
+    buf.reset();
+    assert_eq!(buf.get_pos(), 0);
+    assert_eq!(buf.remaining(), 0);
+    #[cfg(feature = "zeroize")]
+    assert_eq!(buf.get_block(), &[0; 8]);
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn zeroize() {
+    use block_buffer::zeroize::{Zeroize, ZeroizeOnDrop};
+
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+    assert_zeroize_on_drop::<ReadBuffer<[u8; 8]>>();
+    assert_zeroize_on_drop::<ReadBuffer<[u8; 512], u16>>();
+
+    let mut buf = ReadBuffer::<[u8; 512], u16>::default();
+    buf.try_set([0xAA; 512], 300).unwrap();
+    buf.zeroize();
+    assert_eq!(buf.get_pos(), 0);
+    assert!(buf.get_block().iter().all(|&b| b == 0));
+}
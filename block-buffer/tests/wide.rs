@@ -0,0 +1,150 @@
+//! Buffers over blocks which do not fit the default `u8` cursor.
+use array::Array;
+use block_buffer::{BlockBuffer, BufferKind, Cursor, EagerBuffer, Error, LazyBuffer};
+use core::mem::size_of;
+
+#[test]
+fn sizes() {
+    assert_eq!(size_of::<EagerBuffer<[u8; 64]>>(), 65);
+    assert_eq!(size_of::<LazyBuffer<[u8; 64]>>(), 65);
+    assert_eq!(size_of::<EagerBuffer<[u8; 256]>>(), 257);
+    assert_eq!(size_of::<LazyBuffer<[u8; 255]>>(), 256);
+    assert_eq!(size_of::<LazyBuffer<[u8; 256], u16>>(), 258);
+    assert_eq!(size_of::<EagerBuffer<[u8; 512], u16>>(), 514);
+    assert_eq!(size_of::<LazyBuffer<[u8; 512], u16>>(), 514);
+    assert_eq!(size_of::<EagerBuffer<[u8; 1 << 16], u32>>(), (1 << 16) + 4);
+}
+
+fn data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+/// Feed `data` in chunks of `chunk` bytes and return compressed blocks.
+fn digest<B: Array, K: BufferKind>(
+    buf: &mut BlockBuffer<B, K>,
+    data: &[u8],
+    chunk: usize,
+) -> Vec<u8> {
+    let mut out = Vec::new();
+    for c in data.chunks(chunk) {
+        buf.digest_blocks(c, |blocks| {
+            blocks
+                .iter()
+                .for_each(|b| out.extend_from_slice(b.as_ref()))
+        });
+    }
+    out
+}
+
+fn check_eager<B: Array, C: Cursor>() {
+    let bs = B::LEN;
+    let mut buf = EagerBuffer::<B, C>::default();
+    assert_eq!(buf.size(), bs);
+    assert_eq!(buf.get_pos(), 0);
+    assert_eq!(buf.remaining(), bs);
+
+    for &len in &[0, 1, bs - 1, bs, bs + 1, 3 * bs - 1, 3 * bs, 3 * bs + 7] {
+        for &chunk in &[1, 7, bs - 1, bs, bs + 1, 4 * bs] {
+            let msg = data(len);
+            buf.reset();
+            let out = digest(&mut buf, &msg, chunk);
+            let full = len / bs * bs;
+            assert_eq!(out, msg[..full], "len {} chunk {}", len, chunk);
+            assert_eq!(buf.get_pos(), len - full);
+            assert_eq!(buf.get_data(), &msg[full..]);
+            assert_eq!(buf.remaining(), bs - (len - full));
+        }
+    }
+
+    let msg = data(bs);
+    let buf = EagerBuffer::<B, C>::new(&msg[..bs - 1]);
+    assert_eq!(buf.get_pos(), bs - 1);
+    assert_eq!(buf.get_data(), &msg[..bs - 1]);
+    assert_eq!(EagerBuffer::<B, C>::try_new(&msg).err(), Some(Error));
+
+    let mut buf = EagerBuffer::<B, C>::default();
+    let block = *B::try_from_slice(&msg).unwrap();
+    assert_eq!(buf.try_set(block, bs - 1), Ok(()));
+    assert_eq!(buf.get_pos(), bs - 1);
+    assert_eq!(buf.get_block().as_ref(), &msg[..]);
+    assert_eq!(buf.try_set(block, bs), Err(Error));
+    assert_eq!(buf.get_pos(), bs - 1);
+
+    // partial block is padded with zeros
+    let mut buf = EagerBuffer::<B, C>::new(&msg[..bs - 1]);
+    let mut blocks = Vec::new();
+    buf.digest_pad(0x80, &[], |b| blocks.push(*b));
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].as_ref()[..bs - 1], msg[..bs - 1]);
+    assert_eq!(blocks[0].as_ref()[bs - 1], 0x80);
+    assert_eq!(buf.get_pos(), 0);
+}
+
+fn check_lazy<B: Array, C: Cursor>() {
+    let bs = B::LEN;
+    let mut buf = LazyBuffer::<B, C>::default();
+    assert_eq!(buf.size(), bs);
+    assert_eq!(buf.get_pos(), 0);
+
+    for &len in &[0, 1, bs - 1, bs, bs + 1, 3 * bs - 1, 3 * bs, 3 * bs + 7] {
+        for &chunk in &[1, 7, bs - 1, bs, bs + 1, 4 * bs] {
+            let msg = data(len);
+            buf.reset();
+            let out = digest(&mut buf, &msg, chunk);
+            // the last block is kept in the buffer even if it is full
+            let full = if len == 0 { 0 } else { (len - 1) / bs * bs };
+            assert_eq!(out, msg[..full], "len {} chunk {}", len, chunk);
+            assert_eq!(buf.get_pos(), len - full);
+            assert_eq!(buf.get_data(), &msg[full..]);
+            assert_eq!(buf.remaining(), bs - (len - full));
+        }
+    }
+
+    let msg = data(bs + 1);
+    let buf = LazyBuffer::<B, C>::new(&msg[..bs]);
+    assert_eq!(buf.get_pos(), bs);
+    assert_eq!(buf.remaining(), 0);
+    assert_eq!(buf.get_data(), &msg[..bs]);
+    assert_eq!(LazyBuffer::<B, C>::try_new(&msg).err(), Some(Error));
+
+    let mut buf = LazyBuffer::<B, C>::default();
+    let block = *B::try_from_slice(&msg[..bs]).unwrap();
+    assert_eq!(buf.try_set(block, bs), Ok(()));
+    assert_eq!(buf.get_pos(), bs);
+    assert_eq!(buf.try_set(block, bs + 1), Err(Error));
+    assert_eq!(buf.get_pos(), bs);
+}
+
+#[test]
+fn eager_256() {
+    check_eager::<[u8; 256], u8>();
+    check_eager::<[u8; 256], u16>();
+}
+
+#[test]
+fn eager_512() {
+    check_eager::<[u8; 512], u16>();
+    check_eager::<[u8; 512], u32>();
+}
+
+#[test]
+fn lazy_256() {
+    check_lazy::<[u8; 255], u8>();
+    check_lazy::<[u8; 256], u16>();
+}
+
+#[test]
+fn lazy_512() {
+    check_lazy::<[u8; 512], u16>();
+    check_lazy::<[u8; 512], u32>();
+}
+
+#[test]
+fn one_byte_blocks() {
+    let mut buf = EagerBuffer::<[u8; 1]>::default();
+    assert_eq!(digest(&mut buf, &[1, 2, 3], 2), [1, 2, 3]);
+    assert_eq!(buf.get_pos(), 0);
+    let mut buf = LazyBuffer::<[u8; 1]>::default();
+    assert_eq!(digest(&mut buf, &[1, 2, 3], 2), [1, 2]);
+    assert_eq!(buf.get_data(), [3]);
+}
@@ -10,17 +10,20 @@
 //! [2]: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation
 //! [3]: https://en.wikipedia.org/wiki/Symmetric-key_algorithm
 
+use crate::utils::{array_into_buf, into_chunks};
+#[cfg(feature = "block-padding")]
+use crate::utils::{unpad_blocks, PaddedInOutBuf};
 use crate::{ParBlocks, ParBlocksSizeUser};
 #[cfg(all(feature = "block-padding", feature = "alloc"))]
 use alloc::{vec, vec::Vec};
 #[cfg(feature = "block-padding")]
 use inout::{
-    block_padding::{Padding, UnpadError},
+    block_padding::{RawPadding, UnpadError},
     InOutBufReserved, PadError,
 };
 use inout::{InOut, InOutBuf, NotEqualError};
 
-pub use crypto_common::{generic_array::ArrayLength, typenum::Unsigned, Block, BlockSizeUser};
+pub use crypto_common::{array::Array, Block, BlockSizeUser};
 
 /// Marker trait for block ciphers.
 pub trait BlockCipher: BlockSizeUser {}
@@ -32,17 +35,17 @@ pub trait BlockBackend: ParBlocksSizeUser {
 
     /// Process inout blocks in parallel.
     #[inline(always)]
-    fn proc_par_blocks(&mut self, mut blocks: InOut<'_, '_, ParBlocks<Self>>) {
-        for i in 0..Self::ParBlocksSize::USIZE {
-            self.proc_block(blocks.get(i));
+    fn proc_par_blocks(&mut self, blocks: InOut<'_, '_, ParBlocks<Self>>) {
+        for block in array_into_buf(blocks) {
+            self.proc_block(block);
         }
     }
 
     /// Process buffer of inout blocks. Length of the buffer MUST be smaller
-    /// than `Self::ParBlocksSize`.
+    /// than length of `Self::ParBlocks`.
     #[inline(always)]
     fn proc_tail_blocks(&mut self, blocks: InOutBuf<'_, '_, Block<Self>>) {
-        assert!(blocks.len() < Self::ParBlocksSize::USIZE);
+        assert!(blocks.len() < ParBlocks::<Self>::LEN);
         for block in blocks {
             self.proc_block(block);
         }
@@ -61,7 +64,7 @@ pub trait BlockBackend: ParBlocksSizeUser {
     }
 
     /// Process buffer of blocks in-place. Length of the buffer MUST be smaller
-    /// than `Self::ParBlocksSize`.
+    /// than length of `Self::ParBlocks`.
     #[inline(always)]
     fn proc_tail_blocks_inplace(&mut self, blocks: &mut [Block<Self>]) {
         self.proc_tail_blocks(blocks.into());
@@ -73,13 +76,13 @@ pub trait BlockBackend: ParBlocksSizeUser {
 /// This trait is used to define rank-2 closures.
 pub trait BlockClosure: BlockSizeUser {
     /// Execute closure with the provided block cipher backend.
-    fn call<B: BlockBackend<BlockSize = Self::BlockSize>>(self, backend: &mut B);
+    fn call<B: BlockBackend<Block = Self::Block>>(self, backend: &mut B);
 }
 
 /// Encrypt-only functionality for block ciphers.
 pub trait BlockEncrypt: BlockSizeUser + Sized {
     /// Encrypt data using backend provided to the rank-2 closure.
-    fn encrypt_with_backend(&self, f: impl BlockClosure<BlockSize = Self::BlockSize>);
+    fn encrypt_with_backend(&self, f: impl BlockClosure<Block = Self::Block>);
 
     /// Encrypt single `inout` block.
     #[inline]
@@ -134,11 +137,11 @@ pub trait BlockEncrypt: BlockSizeUser + Sized {
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn encrypt_padded_inout<'inp, 'out, P: Padding<Self::BlockSize>>(
+    fn encrypt_padded_inout<'inp, 'out, P: RawPadding>(
         &self,
         data: InOutBufReserved<'inp, 'out, u8>,
     ) -> Result<&'out [u8], PadError> {
-        let mut buf = data.into_padded_blocks::<P, Self::BlockSize>()?;
+        let mut buf = PaddedInOutBuf::<Self::Block>::new::<P>(data)?;
         self.encrypt_blocks_inout(buf.get_blocks());
         if let Some(block) = buf.get_tail_block() {
             self.encrypt_block_inout(block);
@@ -152,7 +155,7 @@ pub trait BlockEncrypt: BlockSizeUser + Sized {
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn encrypt_padded<'a, P: Padding<Self::BlockSize>>(
+    fn encrypt_padded<'a, P: RawPadding>(
         &self,
         buf: &'a mut [u8],
         msg_len: usize,
@@ -167,7 +170,7 @@ pub trait BlockEncrypt: BlockSizeUser + Sized {
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn encrypt_padded_b2b<'a, P: Padding<Self::BlockSize>>(
+    fn encrypt_padded_b2b<'a, P: RawPadding>(
         &self,
         msg: &[u8],
         out_buf: &'a mut [u8],
@@ -180,7 +183,7 @@ pub trait BlockEncrypt: BlockSizeUser + Sized {
     #[cfg(all(feature = "block-padding", feature = "alloc"))]
     #[cfg_attr(docsrs, doc(cfg(all(feature = "block-padding", feature = "alloc"))))]
     #[inline]
-    fn encrypt_padded_vec<P: Padding<Self::BlockSize>>(&self, msg: &[u8]) -> Vec<u8> {
+    fn encrypt_padded_vec<P: RawPadding>(&self, msg: &[u8]) -> Vec<u8> {
         let mut out = allocate_out_vec::<Self>(msg.len());
         let len = self
             .encrypt_padded_b2b::<P>(msg, &mut out)
@@ -194,7 +197,7 @@ pub trait BlockEncrypt: BlockSizeUser + Sized {
 /// Decrypt-only functionality for block ciphers.
 pub trait BlockDecrypt: BlockSizeUser {
     /// Decrypt data using backend provided to the rank-2 closure.
-    fn decrypt_with_backend(&self, f: impl BlockClosure<BlockSize = Self::BlockSize>);
+    fn decrypt_with_backend(&self, f: impl BlockClosure<Block = Self::Block>);
 
     /// Decrypt single `inout` block.
     #[inline]
@@ -246,33 +249,30 @@ pub trait BlockDecrypt: BlockSizeUser {
     /// Decrypt input and unpad it. Returns resulting ciphertext slice.
     ///
     /// Returns [`UnpadError`] if padding is malformed or if input length is
-    /// not multiple of `Self::BlockSize`.
+    /// not multiple of the block size.
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn decrypt_padded_inout<'inp, 'out, P: Padding<Self::BlockSize>>(
+    fn decrypt_padded_inout<'inp, 'out, P: RawPadding>(
         &self,
         data: InOutBuf<'inp, 'out, u8>,
     ) -> Result<&'out [u8], UnpadError> {
-        let (mut blocks, tail) = data.into_chunks();
+        let (mut blocks, tail) = into_chunks::<_, Self::Block>(data);
         if !tail.is_empty() {
             return Err(UnpadError);
         }
         self.decrypt_blocks_inout(blocks.reborrow());
-        P::unpad_blocks(blocks.into_out())
+        unpad_blocks::<P, _>(blocks.into_out())
     }
 
     /// Decrypt input and unpad it in-place. Returns resulting ciphertext slice.
     ///
     /// Returns [`UnpadError`] if padding is malformed or if input length is
-    /// not multiple of `Self::BlockSize`.
+    /// not multiple of the block size.
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn decrypt_padded<'a, P: Padding<Self::BlockSize>>(
-        &self,
-        buf: &'a mut [u8],
-    ) -> Result<&'a [u8], UnpadError> {
+    fn decrypt_padded<'a, P: RawPadding>(&self, buf: &'a mut [u8]) -> Result<&'a [u8], UnpadError> {
         self.decrypt_padded_inout::<P>(buf.into())
     }
 
@@ -280,11 +280,11 @@ pub trait BlockDecrypt: BlockSizeUser {
     /// ciphertext slice.
     ///
     /// Returns [`UnpadError`] if padding is malformed or if input length is
-    /// not multiple of `Self::BlockSize`.
+    /// not multiple of the block size.
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn decrypt_padded_b2b<'a, P: Padding<Self::BlockSize>>(
+    fn decrypt_padded_b2b<'a, P: RawPadding>(
         &self,
         in_buf: &[u8],
         out_buf: &'a mut [u8],
@@ -302,14 +302,11 @@ pub trait BlockDecrypt: BlockSizeUser {
     /// ciphertext Vec.
     ///
     /// Returns [`UnpadError`] if padding is malformed or if input length is
-    /// not multiple of `Self::BlockSize`.
+    /// not multiple of the block size.
     #[cfg(all(feature = "block-padding", feature = "alloc"))]
     #[cfg_attr(docsrs, doc(cfg(all(feature = "block-padding", feature = "alloc"))))]
     #[inline]
-    fn decrypt_padded_vec<P: Padding<Self::BlockSize>>(
-        &self,
-        buf: &[u8],
-    ) -> Result<Vec<u8>, UnpadError> {
+    fn decrypt_padded_vec<P: RawPadding>(&self, buf: &[u8]) -> Result<Vec<u8>, UnpadError> {
         let mut out = vec![0; buf.len()];
         let len = self.decrypt_padded_b2b::<P>(buf, &mut out)?.len();
         out.truncate(len);
@@ -324,7 +321,7 @@ pub trait BlockDecrypt: BlockSizeUser {
 /// underlying hardware peripheral.
 pub trait BlockEncryptMut: BlockSizeUser + Sized {
     /// Encrypt data using backend provided to the rank-2 closure.
-    fn encrypt_with_backend_mut(&mut self, f: impl BlockClosure<BlockSize = Self::BlockSize>);
+    fn encrypt_with_backend_mut(&mut self, f: impl BlockClosure<Block = Self::Block>);
 
     /// Encrypt single `inout` block.
     #[inline]
@@ -379,11 +376,11 @@ pub trait BlockEncryptMut: BlockSizeUser + Sized {
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn encrypt_padded_inout_mut<'inp, 'out, P: Padding<Self::BlockSize>>(
+    fn encrypt_padded_inout_mut<'inp, 'out, P: RawPadding>(
         mut self,
         data: InOutBufReserved<'inp, 'out, u8>,
     ) -> Result<&'out [u8], PadError> {
-        let mut buf = data.into_padded_blocks::<P, Self::BlockSize>()?;
+        let mut buf = PaddedInOutBuf::<Self::Block>::new::<P>(data)?;
         self.encrypt_blocks_inout_mut(buf.get_blocks());
         if let Some(block) = buf.get_tail_block() {
             self.encrypt_block_inout_mut(block);
@@ -397,7 +394,7 @@ pub trait BlockEncryptMut: BlockSizeUser + Sized {
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn encrypt_padded_mut<P: Padding<Self::BlockSize>>(
+    fn encrypt_padded_mut<P: RawPadding>(
         self,
         buf: &mut [u8],
         msg_len: usize,
@@ -412,7 +409,7 @@ pub trait BlockEncryptMut: BlockSizeUser + Sized {
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn encrypt_padded_b2b_mut<'a, P: Padding<Self::BlockSize>>(
+    fn encrypt_padded_b2b_mut<'a, P: RawPadding>(
         self,
         msg: &[u8],
         out_buf: &'a mut [u8],
@@ -425,7 +422,7 @@ pub trait BlockEncryptMut: BlockSizeUser + Sized {
     #[cfg(all(feature = "block-padding", feature = "alloc"))]
     #[cfg_attr(docsrs, doc(cfg(all(feature = "block-padding", feature = "alloc"))))]
     #[inline]
-    fn encrypt_padded_vec_mut<P: Padding<Self::BlockSize>>(self, msg: &[u8]) -> Vec<u8> {
+    fn encrypt_padded_vec_mut<P: RawPadding>(self, msg: &[u8]) -> Vec<u8> {
         let mut out = allocate_out_vec::<Self>(msg.len());
         let len = self
             .encrypt_padded_b2b_mut::<P>(msg, &mut out)
@@ -443,7 +440,7 @@ pub trait BlockEncryptMut: BlockSizeUser + Sized {
 /// underlying hardware peripheral.
 pub trait BlockDecryptMut: BlockSizeUser + Sized {
     /// Decrypt data using backend provided to the rank-2 closure.
-    fn decrypt_with_backend_mut(&mut self, f: impl BlockClosure<BlockSize = Self::BlockSize>);
+    fn decrypt_with_backend_mut(&mut self, f: impl BlockClosure<Block = Self::Block>);
 
     /// Decrypt single `inout` block.
     #[inline]
@@ -495,33 +492,30 @@ pub trait BlockDecryptMut: BlockSizeUser + Sized {
     /// Decrypt input and unpad it. Returns resulting ciphertext slice.
     ///
     /// Returns [`UnpadError`] if padding is malformed or if input length is
-    /// not multiple of `Self::BlockSize`.
+    /// not multiple of the block size.
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn decrypt_padded_inout_mut<'inp, 'out, P: Padding<Self::BlockSize>>(
+    fn decrypt_padded_inout_mut<'inp, 'out, P: RawPadding>(
         mut self,
         data: InOutBuf<'inp, 'out, u8>,
     ) -> Result<&'out [u8], UnpadError> {
-        let (mut blocks, tail) = data.into_chunks();
+        let (mut blocks, tail) = into_chunks::<_, Self::Block>(data);
         if !tail.is_empty() {
             return Err(UnpadError);
         }
         self.decrypt_blocks_inout_mut(blocks.reborrow());
-        P::unpad_blocks(blocks.into_out())
+        unpad_blocks::<P, _>(blocks.into_out())
     }
 
     /// Decrypt input and unpad it in-place. Returns resulting ciphertext slice.
     ///
     /// Returns [`UnpadError`] if padding is malformed or if input length is
-    /// not multiple of `Self::BlockSize`.
+    /// not multiple of the block size.
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn decrypt_padded_mut<P: Padding<Self::BlockSize>>(
-        self,
-        buf: &mut [u8],
-    ) -> Result<&[u8], UnpadError> {
+    fn decrypt_padded_mut<P: RawPadding>(self, buf: &mut [u8]) -> Result<&[u8], UnpadError> {
         self.decrypt_padded_inout_mut::<P>(buf.into())
     }
 
@@ -529,11 +523,11 @@ pub trait BlockDecryptMut: BlockSizeUser + Sized {
     /// ciphertext slice.
     ///
     /// Returns [`UnpadError`] if padding is malformed or if input length is
-    /// not multiple of `Self::BlockSize`.
+    /// not multiple of the block size.
     #[cfg(feature = "block-padding")]
     #[cfg_attr(docsrs, doc(cfg(feature = "block-padding")))]
     #[inline]
-    fn decrypt_padded_b2b_mut<'a, P: Padding<Self::BlockSize>>(
+    fn decrypt_padded_b2b_mut<'a, P: RawPadding>(
         self,
         in_buf: &[u8],
         out_buf: &'a mut [u8],
@@ -551,14 +545,11 @@ pub trait BlockDecryptMut: BlockSizeUser + Sized {
     /// ciphertext Vec.
     ///
     /// Returns [`UnpadError`] if padding is malformed or if input length is
-    /// not multiple of `Self::BlockSize`.
+    /// not multiple of the block size.
     #[cfg(all(feature = "block-padding", feature = "alloc"))]
     #[cfg_attr(docsrs, doc(cfg(all(feature = "block-padding", feature = "alloc"))))]
     #[inline]
-    fn decrypt_padded_vec_mut<P: Padding<Self::BlockSize>>(
-        self,
-        buf: &[u8],
-    ) -> Result<Vec<u8>, UnpadError> {
+    fn decrypt_padded_vec_mut<P: RawPadding>(self, buf: &[u8]) -> Result<Vec<u8>, UnpadError> {
         let mut out = vec![0; buf.len()];
         let len = self.decrypt_padded_b2b_mut::<P>(buf, &mut out)?.len();
         out.truncate(len);
@@ -567,13 +558,13 @@ pub trait BlockDecryptMut: BlockSizeUser + Sized {
 }
 
 impl<Alg: BlockEncrypt> BlockEncryptMut for Alg {
-    fn encrypt_with_backend_mut(&mut self, f: impl BlockClosure<BlockSize = Self::BlockSize>) {
+    fn encrypt_with_backend_mut(&mut self, f: impl BlockClosure<Block = Self::Block>) {
         self.encrypt_with_backend(f);
     }
 }
 
 impl<Alg: BlockDecrypt> BlockDecryptMut for Alg {
-    fn decrypt_with_backend_mut(&mut self, f: impl BlockClosure<BlockSize = Self::BlockSize>) {
+    fn decrypt_with_backend_mut(&mut self, f: impl BlockClosure<Block = Self::Block>) {
         self.decrypt_with_backend(f);
     }
 }
@@ -581,47 +572,47 @@ impl<Alg: BlockDecrypt> BlockDecryptMut for Alg {
 impl<Alg: BlockCipher> BlockCipher for &Alg {}
 
 impl<Alg: BlockEncrypt> BlockEncrypt for &Alg {
-    fn encrypt_with_backend(&self, f: impl BlockClosure<BlockSize = Self::BlockSize>) {
+    fn encrypt_with_backend(&self, f: impl BlockClosure<Block = Self::Block>) {
         Alg::encrypt_with_backend(self, f);
     }
 }
 
 impl<Alg: BlockDecrypt> BlockDecrypt for &Alg {
-    fn decrypt_with_backend(&self, f: impl BlockClosure<BlockSize = Self::BlockSize>) {
+    fn decrypt_with_backend(&self, f: impl BlockClosure<Block = Self::Block>) {
         Alg::decrypt_with_backend(self, f);
     }
 }
 
 /// Closure used in methods which operate over separate blocks.
-struct BlockCtx<'inp, 'out, BS: ArrayLength<u8>> {
-    block: InOut<'inp, 'out, Block<Self>>,
+struct BlockCtx<'inp, 'out, B: Array> {
+    block: InOut<'inp, 'out, B>,
 }
 
-impl<'inp, 'out, BS: ArrayLength<u8>> BlockSizeUser for BlockCtx<'inp, 'out, BS> {
-    type BlockSize = BS;
+impl<'inp, 'out, B: Array> BlockSizeUser for BlockCtx<'inp, 'out, B> {
+    type Block = B;
 }
 
-impl<'inp, 'out, BS: ArrayLength<u8>> BlockClosure for BlockCtx<'inp, 'out, BS> {
+impl<'inp, 'out, B: Array> BlockClosure for BlockCtx<'inp, 'out, B> {
     #[inline(always)]
-    fn call<B: BlockBackend<BlockSize = BS>>(self, backend: &mut B) {
+    fn call<Back: BlockBackend<Block = B>>(self, backend: &mut Back) {
         backend.proc_block(self.block);
     }
 }
 
 /// Closure used in methods which operate over slice of blocks.
-struct BlocksCtx<'inp, 'out, BS: ArrayLength<u8>> {
-    blocks: InOutBuf<'inp, 'out, Block<Self>>,
+struct BlocksCtx<'inp, 'out, B: Array> {
+    blocks: InOutBuf<'inp, 'out, B>,
 }
 
-impl<'inp, 'out, BS: ArrayLength<u8>> BlockSizeUser for BlocksCtx<'inp, 'out, BS> {
-    type BlockSize = BS;
+impl<'inp, 'out, B: Array> BlockSizeUser for BlocksCtx<'inp, 'out, B> {
+    type Block = B;
 }
 
-impl<'inp, 'out, BS: ArrayLength<u8>> BlockClosure for BlocksCtx<'inp, 'out, BS> {
+impl<'inp, 'out, B: Array> BlockClosure for BlocksCtx<'inp, 'out, B> {
     #[inline(always)]
-    fn call<B: BlockBackend<BlockSize = BS>>(self, backend: &mut B) {
-        if B::ParBlocksSize::USIZE > 1 {
-            let (chunks, tail) = self.blocks.into_chunks();
+    fn call<Back: BlockBackend<Block = B>>(self, backend: &mut Back) {
+        if ParBlocks::<Back>::LEN > 1 {
+            let (chunks, tail) = into_chunks::<_, Back::ParBlocks>(self.blocks);
             for chunk in chunks {
                 backend.proc_par_blocks(chunk);
             }
@@ -636,7 +627,7 @@ impl<'inp, 'out, BS: ArrayLength<u8>> BlockClosure for BlocksCtx<'inp, 'out, BS>
 
 #[cfg(all(feature = "block-padding", feature = "alloc"))]
 fn allocate_out_vec<BS: BlockSizeUser>(len: usize) -> Vec<u8> {
-    let bs = BS::BlockSize::USIZE;
+    let bs = <BS::Block as Array>::LEN;
     vec![0; bs * (len / bs + 1)]
 }
 
@@ -645,24 +636,24 @@ fn allocate_out_vec<BS: BlockSizeUser>(len: usize) -> Vec<u8> {
 macro_rules! impl_simple_block_encdec {
     (
         <$($N:ident$(:$b0:ident$(+$b:ident)*)?),*>
-        $cipher:ident, $block_size:ty, $state:ident, $block:ident,
+        $cipher:ident, $block_ty:ty, $state:ident, $block:ident,
         encrypt: $enc_block:block
         decrypt: $dec_block:block
     ) => {
         impl<$($N$(:$b0$(+$b)*)?),*> $crate::BlockSizeUser for $cipher<$($N),*> {
-            type BlockSize = $block_size;
+            type Block = $block_ty;
         }
 
         impl<$($N$(:$b0$(+$b)*)?),*> $crate::BlockEncrypt for $cipher<$($N),*> {
-            fn encrypt_with_backend(&self, f: impl $crate::BlockClosure<BlockSize = $block_size>) {
+            fn encrypt_with_backend(&self, f: impl $crate::BlockClosure<Block = $block_ty>) {
                 struct EncBack<'a, $($N$(:$b0$(+$b)*)?),* >(&'a $cipher<$($N),*>);
 
                 impl<'a, $($N$(:$b0$(+$b)*)?),* > $crate::BlockSizeUser for EncBack<'a, $($N),*> {
-                    type BlockSize = $block_size;
+                    type Block = $block_ty;
                 }
 
                 impl<'a, $($N$(:$b0$(+$b)*)?),* > $crate::ParBlocksSizeUser for EncBack<'a, $($N),*> {
-                    type ParBlocksSize = $crate::consts::U1;
+                    type ParBlocks = [$block_ty; 1];
                 }
 
                 impl<'a, $($N$(:$b0$(+$b)*)?),* > $crate::BlockBackend for EncBack<'a, $($N),*> {
@@ -681,15 +672,15 @@ macro_rules! impl_simple_block_encdec {
         }
 
         impl<$($N$(:$b0$(+$b)*)?),*> $crate::BlockDecrypt for $cipher<$($N),*> {
-            fn decrypt_with_backend(&self, f: impl $crate::BlockClosure<BlockSize = $block_size>) {
+            fn decrypt_with_backend(&self, f: impl $crate::BlockClosure<Block = $block_ty>) {
                 struct DecBack<'a, $($N$(:$b0$(+$b)*)?),* >(&'a $cipher<$($N),*>);
 
                 impl<'a, $($N$(:$b0$(+$b)*)?),* > $crate::BlockSizeUser for DecBack<'a, $($N),*> {
-                    type BlockSize = $block_size;
+                    type Block = $block_ty;
                 }
 
                 impl<'a, $($N$(:$b0$(+$b)*)?),* > $crate::ParBlocksSizeUser for DecBack<'a, $($N),*> {
-                    type ParBlocksSize = $crate::consts::U1;
+                    type ParBlocks = [$block_ty; 1];
                 }
 
                 impl<'a, $($N$(:$b0$(+$b)*)?),* > $crate::BlockBackend for DecBack<'a, $($N),*> {
@@ -708,12 +699,12 @@ macro_rules! impl_simple_block_encdec {
         }
     };
     (
-        $cipher:ident, $block_size:ty, $state:ident, $block:ident,
+        $cipher:ident, $block_ty:ty, $state:ident, $block:ident,
         encrypt: $enc_block:block
         decrypt: $dec_block:block
     ) => {
         $crate::impl_simple_block_encdec!(
-            <> $cipher, $block_size, $state, $block,
+            <> $cipher, $block_ty, $state, $block,
             encrypt: $enc_block
             decrypt: $dec_block
         );
@@ -1,7 +1,5 @@
 //! Development-related functionality
 
-pub use blobby;
-
 /// Define block cipher test
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "dev")))]
@@ -9,22 +7,19 @@ macro_rules! block_cipher_test {
     ($name:ident, $test_name:expr, $cipher:ty $(,)?) => {
         #[test]
         fn $name() {
-            use cipher::{
-                blobby::Blob3Iterator, generic_array::GenericArray, typenum::Unsigned,
-                BlockDecryptMut, BlockEncryptMut, BlockSizeUser, KeyInit,
-            };
+            use cipher::{blobby::Blob3Iterator, Array, BlockDecryptMut, BlockEncryptMut, KeyInit};
 
             fn run_test(key: &[u8], pt: &[u8], ct: &[u8]) -> bool {
                 let mut state = <$cipher as KeyInit>::new_from_slice(key).unwrap();
 
-                let mut block = GenericArray::clone_from_slice(pt);
+                let mut block = *cipher::Block::<$cipher>::try_from_slice(pt).unwrap();
                 state.encrypt_block_mut(&mut block);
-                if ct != block.as_slice() {
+                if ct != block.as_ref() {
                     return false;
                 }
 
                 state.decrypt_block_mut(&mut block);
-                if pt != block.as_slice() {
+                if pt != block.as_ref() {
                     return false;
                 }
 
@@ -36,10 +31,11 @@ macro_rules! block_cipher_test {
 
                 let mut state = <$cipher as KeyInit>::new_from_slice(key).unwrap();
 
-                let block = Block::clone_from_slice(pt);
+                let block = *Block::try_from_slice(pt).unwrap();
                 let mut blocks1 = vec![block; 101];
                 for (i, b) in blocks1.iter_mut().enumerate() {
                     *b = block;
+                    let b = b.as_mut();
                     b[0] = b[0].wrapping_add(i as u8);
                 }
                 let mut blocks2 = blocks1.clone();
@@ -104,33 +100,36 @@ macro_rules! block_mode_enc_test {
     ($name:ident, $test_name:expr, $cipher:ty $(,)?) => {
         #[test]
         fn $name() {
-            use cipher::{
-                blobby::Blob4Iterator, generic_array::GenericArray, inout::InOutBuf,
-                typenum::Unsigned, BlockEncryptMut, BlockSizeUser, KeyIvInit,
-            };
+            use cipher::{blobby::Blob4Iterator, Array, BlockEncryptMut, KeyIvInit};
+
+            type Block = cipher::Block<$cipher>;
 
             fn run_test(key: &[u8], iv: &[u8], pt: &[u8], ct: &[u8]) -> bool {
                 assert_eq!(pt.len(), ct.len());
                 // test block-by-block processing
                 let mut state = <$cipher as KeyIvInit>::new_from_slices(key, iv).unwrap();
 
+                let bs = Block::LEN;
+                assert_eq!(pt.len() % bs, 0);
                 let mut out = vec![0u8; ct.len()];
-                let mut buf = InOutBuf::new(pt, &mut out).unwrap();
-                let (blocks, tail) = buf.reborrow().into_chunks();
-                assert_eq!(tail.len(), 0);
-                for block in blocks {
-                    state.encrypt_block_inout_mut(block);
+                for (in_block, out_block) in pt.chunks(bs).zip(out.chunks_mut(bs)) {
+                    let in_block = Block::try_from_slice(in_block).unwrap();
+                    let out_block = Block::try_from_mut_slice(out_block).unwrap();
+                    state.encrypt_block_b2b_mut(in_block, out_block);
                 }
-                if buf.get_out() != ct {
+                if out != ct {
                     return false;
                 }
 
                 // test multi-block processing
                 let mut state = <$cipher as KeyIvInit>::new_from_slices(key, iv).unwrap();
-                buf.get_out().iter_mut().for_each(|b| *b = 0);
-                let (blocks, _) = buf.reborrow().into_chunks();
-                state.encrypt_blocks_inout_mut(blocks);
-                if buf.get_out() != ct {
+                let mut blocks: Vec<Block> = pt
+                    .chunks(bs)
+                    .map(|c| *Block::try_from_slice(c).unwrap())
+                    .collect();
+                state.encrypt_blocks_mut(&mut blocks);
+                let out: Vec<u8> = blocks.iter().flat_map(|b| b.as_ref()).copied().collect();
+                if out != ct {
                     return false;
                 }
 
@@ -163,33 +162,36 @@ macro_rules! block_mode_dec_test {
     ($name:ident, $test_name:expr, $cipher:ty $(,)?) => {
         #[test]
         fn $name() {
-            use cipher::{
-                blobby::Blob4Iterator, generic_array::GenericArray, inout::InOutBuf,
-                typenum::Unsigned, BlockDecryptMut, BlockSizeUser, KeyIvInit,
-            };
+            use cipher::{blobby::Blob4Iterator, Array, BlockDecryptMut, KeyIvInit};
+
+            type Block = cipher::Block<$cipher>;
 
             fn run_test(key: &[u8], iv: &[u8], pt: &[u8], ct: &[u8]) -> bool {
                 assert_eq!(pt.len(), ct.len());
                 // test block-by-block processing
                 let mut state = <$cipher as KeyIvInit>::new_from_slices(key, iv).unwrap();
 
+                let bs = Block::LEN;
+                assert_eq!(ct.len() % bs, 0);
                 let mut out = vec![0u8; pt.len()];
-                let mut buf = InOutBuf::new(ct, &mut out).unwrap();
-                let (blocks, tail) = buf.reborrow().into_chunks();
-                assert_eq!(tail.len(), 0);
-                for block in blocks {
-                    state.decrypt_block_inout_mut(block);
+                for (in_block, out_block) in ct.chunks(bs).zip(out.chunks_mut(bs)) {
+                    let in_block = Block::try_from_slice(in_block).unwrap();
+                    let out_block = Block::try_from_mut_slice(out_block).unwrap();
+                    state.decrypt_block_b2b_mut(in_block, out_block);
                 }
-                if buf.get_out() != pt {
+                if out != pt {
                     return false;
                 }
 
                 // test multi-block processing
                 let mut state = <$cipher as KeyIvInit>::new_from_slices(key, iv).unwrap();
-                buf.get_out().iter_mut().for_each(|b| *b = 0);
-                let (blocks, _) = buf.reborrow().into_chunks();
-                state.decrypt_blocks_inout_mut(blocks);
-                if buf.get_out() != pt {
+                let mut blocks: Vec<Block> = ct
+                    .chunks(bs)
+                    .map(|c| *Block::try_from_slice(c).unwrap())
+                    .collect();
+                state.decrypt_blocks_mut(&mut blocks);
+                let out: Vec<u8> = blocks.iter().flat_map(|b| b.as_ref()).copied().collect();
+                if out != pt {
                     return false;
                 }
 
@@ -233,30 +235,30 @@ macro_rules! iv_state_test {
         fn $name() {
             use cipher::*;
 
-            let mut blocks = [Block::<$cipher>::default(); 32];
+            let mut blocks = [Block::<$cipher>::zero(); 32];
 
             for (i, block) in blocks.iter_mut().enumerate() {
-                for (j, b) in block.iter_mut().enumerate() {
+                for (j, b) in block.as_mut().iter_mut().enumerate() {
                     *b = (i + j) as u8;
                 }
             }
 
-            let mut key = Key::<$cipher>::default();
-            let mut iv = Iv::<$cipher>::default();
-            key.iter_mut().for_each(|b| *b = 0x42);
-            iv.iter_mut().for_each(|b| *b = 0x24);
+            let mut key = Key::<$cipher>::zero();
+            let mut iv = Iv::<$cipher>::zero();
+            key.as_mut().iter_mut().for_each(|b| *b = 0x42);
+            iv.as_mut().iter_mut().for_each(|b| *b = 0x24);
 
-            let mut cipher = <$cipher>::new(&key, &iv);
+            let mut cipher = <$cipher>::new(key, iv);
             let mut target = blocks.clone();
             cipher.$method(&mut target);
 
             for i in 0..32 {
                 let mut blocks = blocks.clone();
                 let (b1, b2) = blocks.split_at_mut(i);
-                let mut cipher1 = <$cipher>::new(&key, &iv);
+                let mut cipher1 = <$cipher>::new(key, iv);
                 cipher1.$method(b1);
                 let temp_iv = cipher1.iv_state();
-                let mut cipher2 = <$cipher>::new(&key, &temp_iv);
+                let mut cipher2 = <$cipher>::new(key, temp_iv);
                 cipher2.$method(b2);
                 assert_eq!(blocks, target);
             }
@@ -271,9 +273,9 @@ macro_rules! block_encryptor_bench {
     (Key: $cipher:ty, $block_name:ident, $blocks_name:ident $(,)? ) => {
         $crate::block_encryptor_bench!(
             {
-                use $crate::KeyInit;
-                let key = test::black_box(Default::default());
-                <$cipher>::new(&key)
+                use $crate::{Array, KeyInit};
+                let key = test::black_box(Array::zero());
+                <$cipher>::new(key)
             },
             $cipher,
             $block_name,
@@ -283,10 +285,10 @@ macro_rules! block_encryptor_bench {
     (KeyIv: $cipher:ty, $block_name:ident, $blocks_name:ident $(,)? ) => {
         $crate::block_encryptor_bench!(
             {
-                use $crate::KeyIvInit;
-                let key = test::black_box(Default::default());
-                let iv = test::black_box(Default::default());
-                <$cipher>::new(&key, &iv)
+                use $crate::{Array, KeyIvInit};
+                let key = test::black_box(Array::zero());
+                let iv = test::black_box(Array::zero());
+                <$cipher>::new(key, iv)
             },
             $cipher,
             $block_name,
@@ -296,10 +298,10 @@ macro_rules! block_encryptor_bench {
     ($init:block, $cipher:ty, $block_name:ident, $blocks_name:ident $(,)? ) => {
         #[bench]
         pub fn $block_name(bh: &mut test::Bencher) {
-            use cipher::BlockEncryptMut;
+            use cipher::{Array, BlockEncryptMut};
 
             let mut cipher = $init;
-            let mut blocks = vec![Default::default(); 1024];
+            let mut blocks = vec![cipher::Block::<$cipher>::zero(); 1024];
 
             bh.iter(|| {
                 for block in blocks.iter_mut() {
@@ -307,21 +309,21 @@ macro_rules! block_encryptor_bench {
                 }
                 test::black_box(&blocks);
             });
-            bh.bytes = (blocks.len() * blocks[0].len()) as u64;
+            bh.bytes = (blocks.len() * cipher::Block::<$cipher>::LEN) as u64;
         }
 
         #[bench]
         pub fn $blocks_name(bh: &mut test::Bencher) {
-            use cipher::BlockEncryptMut;
+            use cipher::{Array, BlockEncryptMut};
 
             let mut cipher = $init;
-            let mut blocks = vec![Default::default(); 1024];
+            let mut blocks = vec![cipher::Block::<$cipher>::zero(); 1024];
 
             bh.iter(|| {
                 cipher.encrypt_blocks_mut(&mut blocks);
                 test::black_box(&blocks);
             });
-            bh.bytes = (blocks.len() * blocks[0].len()) as u64;
+            bh.bytes = (blocks.len() * cipher::Block::<$cipher>::LEN) as u64;
         }
     };
 }
@@ -333,9 +335,9 @@ macro_rules! block_decryptor_bench {
     (Key: $cipher:ty, $block_name:ident, $blocks_name:ident $(,)? ) => {
         $crate::block_decryptor_bench!(
             {
-                use $crate::KeyInit;
-                let key = test::black_box(Default::default());
-                <$cipher>::new(&key)
+                use $crate::{Array, KeyInit};
+                let key = test::black_box(Array::zero());
+                <$cipher>::new(key)
             },
             $cipher,
             $block_name,
@@ -345,10 +347,10 @@ macro_rules! block_decryptor_bench {
     (KeyIv: $cipher:ty, $block_name:ident, $blocks_name:ident $(,)? ) => {
         $crate::block_decryptor_bench!(
             {
-                use $crate::KeyIvInit;
-                let key = test::black_box(Default::default());
-                let iv = test::black_box(Default::default());
-                <$cipher>::new(&key, &iv)
+                use $crate::{Array, KeyIvInit};
+                let key = test::black_box(Array::zero());
+                let iv = test::black_box(Array::zero());
+                <$cipher>::new(key, iv)
             },
             $cipher,
             $block_name,
@@ -358,10 +360,10 @@ macro_rules! block_decryptor_bench {
     ($init:block, $cipher:ty, $block_name:ident, $blocks_name:ident $(,)? ) => {
         #[bench]
         pub fn $block_name(bh: &mut test::Bencher) {
-            use cipher::BlockDecryptMut;
+            use cipher::{Array, BlockDecryptMut};
 
             let mut cipher = $init;
-            let mut blocks = vec![Default::default(); 1024];
+            let mut blocks = vec![cipher::Block::<$cipher>::zero(); 1024];
 
             bh.iter(|| {
                 for block in blocks.iter_mut() {
@@ -369,21 +371,21 @@ macro_rules! block_decryptor_bench {
                 }
                 test::black_box(&blocks);
             });
-            bh.bytes = (blocks.len() * blocks[0].len()) as u64;
+            bh.bytes = (blocks.len() * cipher::Block::<$cipher>::LEN) as u64;
         }
 
         #[bench]
         pub fn $blocks_name(bh: &mut test::Bencher) {
-            use cipher::BlockDecryptMut;
+            use cipher::{Array, BlockDecryptMut};
 
             let mut cipher = $init;
-            let mut blocks = vec![Default::default(); 1024];
+            let mut blocks = vec![cipher::Block::<$cipher>::zero(); 1024];
 
             bh.iter(|| {
                 cipher.decrypt_blocks_mut(&mut blocks);
                 test::black_box(&blocks);
             });
-            bh.bytes = (blocks.len() * blocks[0].len()) as u64;
+            bh.bytes = (blocks.len() * cipher::Block::<$cipher>::LEN) as u64;
         }
     };
 }
@@ -7,7 +7,6 @@ macro_rules! stream_cipher_test {
     ($name:ident, $test_name:expr, $cipher:ty $(,)?) => {
         #[test]
         fn $name() {
-            use cipher::generic_array::GenericArray;
             use cipher::{blobby::Blob4Iterator, KeyIvInit, StreamCipher};
 
             let data = include_bytes!(concat!("data/", $test_name, ".blb"));
@@ -43,11 +42,10 @@ macro_rules! stream_cipher_seek_test {
     ($name:ident, $cipher:ty) => {
         #[test]
         fn $name() {
-            use cipher::generic_array::GenericArray;
-            use cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
+            use cipher::{Array, KeyIvInit, StreamCipher, StreamCipherSeek};
 
             fn get_cipher() -> $cipher {
-                <$cipher>::new(&Default::default(), &Default::default())
+                <$cipher>::new(Array::zero(), Array::zero())
             }
 
             const MAX_SEEK: usize = 512;
@@ -99,10 +97,10 @@ macro_rules! stream_cipher_bench {
     ) => {
         $crate::stream_cipher_bench!(
             Init: {
-                use $crate::KeyIvInit;
-                let key = test::black_box(Default::default());
-                let iv = test::black_box(Default::default());
-                <$cipher>::new(&key, &iv)
+                use $crate::{Array, KeyIvInit};
+                let key = test::black_box(Array::zero());
+                let iv = test::black_box(Array::zero());
+                <$cipher>::new(key, iv)
             };
             $($name $bs;)*
         );
@@ -113,10 +111,9 @@ macro_rules! stream_cipher_bench {
     ) => {
         $crate::stream_cipher_bench!(
             Init: {
-                use $crate::KeyInit;
-                let key = test::black_box(Default::default());
-                let iv = test::black_box(Default::default());
-                <$cipher>::new(&key, &iv)
+                use $crate::{Array, KeyInit};
+                let key = test::black_box(Array::zero());
+                <$cipher>::new(key)
             };
             $($name $bs;)*
         );
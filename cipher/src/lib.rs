@@ -33,7 +33,7 @@ pub use inout::block_padding;
 
 #[cfg(feature = "zeroize")]
 #[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
-pub use zeroize;
+pub use block_buffer::zeroize;
 
 #[cfg(feature = "dev")]
 pub use blobby;
@@ -45,15 +45,14 @@ mod errors;
 mod stream;
 mod stream_core;
 mod stream_wrapper;
+mod utils;
 
 pub use crate::{block::*, errors::*, stream::*, stream_core::*, stream_wrapper::*};
 pub use crypto_common::{
-    generic_array,
-    typenum::{self, consts},
-    AlgorithmName, Block, InnerIvInit, InvalidLength, Iv, IvSizeUser, Key, KeyInit, KeyIvInit,
-    KeySizeUser,
+    array, AlgorithmInfo, AlgorithmMetadata, AlgorithmName, Block, InnerInit, InnerIv, InnerIvInit,
+    InnerUser, InvalidLength, Iv, IvSizeUser, Key, KeyInit, KeyIvInit, KeySizeUser, Lengths,
+    WrapperIv,
 };
-use generic_array::{ArrayLength, GenericArray};
 
 /// Trait for loading current IV state.
 pub trait IvState: IvSizeUser {
@@ -63,9 +62,9 @@ pub trait IvState: IvSizeUser {
 
 /// Types which process blocks in parallel.
 pub trait ParBlocksSizeUser: BlockSizeUser {
-    /// Number of blocks which can be processed in parallel.
-    type ParBlocksSize: ArrayLength<Block<Self>>;
+    /// Array of blocks which can be processed in parallel, e.g. `[[u8; 16]; 8]`.
+    type ParBlocks: Array<Block<Self>>;
 }
 
 /// Parallel blocks on which [`ParBlocksSizeUser`] implementors operate.
-pub type ParBlocks<T> = GenericArray<Block<T>, <T as ParBlocksSizeUser>::ParBlocksSize>;
+pub type ParBlocks<T> = <T as ParBlocksSizeUser>::ParBlocks;
@@ -5,7 +5,8 @@
 
 use crate::errors::{OverflowError, StreamCipherError};
 use crate::stream_core::Counter;
-use crate::{Block, BlockDecryptMut, BlockEncryptMut};
+use crate::utils::into_chunks;
+use crate::{Array, Block, BlockDecryptMut, BlockEncryptMut};
 use inout::{InOutBuf, NotEqualError};
 
 /// Marker trait for block-level asynchronous stream ciphers
@@ -15,14 +16,14 @@ pub trait AsyncStreamCipher: Sized {
     where
         Self: BlockEncryptMut,
     {
-        let (blocks, mut tail) = data.into_chunks();
+        let (blocks, mut tail) = into_chunks::<_, Block<Self>>(data);
         self.encrypt_blocks_inout_mut(blocks);
-        let mut block = Block::<Self>::default();
+        let mut block = Block::<Self>::zero();
         let n = tail.len();
         if n != 0 {
-            block[..n].copy_from_slice(tail.get_in());
+            block.as_mut()[..n].copy_from_slice(tail.get_in());
             self.encrypt_block_mut(&mut block);
-            tail.get_out().copy_from_slice(&block[..n]);
+            tail.get_out().copy_from_slice(&block.as_ref()[..n]);
         }
     }
 
@@ -31,14 +32,14 @@ pub trait AsyncStreamCipher: Sized {
     where
         Self: BlockDecryptMut,
     {
-        let (blocks, mut tail) = data.into_chunks();
+        let (blocks, mut tail) = into_chunks::<_, Block<Self>>(data);
         self.decrypt_blocks_inout_mut(blocks);
-        let mut block = Block::<Self>::default();
+        let mut block = Block::<Self>::zero();
         let n = tail.len();
         if n != 0 {
-            block[..n].copy_from_slice(tail.get_in());
+            block.as_mut()[..n].copy_from_slice(tail.get_in());
             self.decrypt_block_mut(&mut block);
-            tail.get_out().copy_from_slice(&block[..n]);
+            tail.get_out().copy_from_slice(&block.as_ref()[..n]);
         }
     }
     /// Encrypt data in place.
@@ -192,31 +193,33 @@ impl<C: StreamCipher> StreamCipher for &mut C {
 pub trait SeekNum: Sized {
     /// Try to get position for block number `block`, byte position inside
     /// block `byte`, and block size `bs`.
-    fn from_block_byte<T: Counter>(block: T, byte: u8, bs: u8) -> Result<Self, OverflowError>;
+    fn from_block_byte<T: Counter>(block: T, byte: usize, bs: usize)
+        -> Result<Self, OverflowError>;
 
     /// Try to get block number and bytes position for given block size `bs`.
-    fn into_block_byte<T: Counter>(self, bs: u8) -> Result<(T, u8), OverflowError>;
+    fn into_block_byte<T: Counter>(self, bs: usize) -> Result<(T, usize), OverflowError>;
 }
 
 macro_rules! impl_seek_num {
     {$($t:ty )*} => {
         $(
             impl SeekNum for $t {
-                fn from_block_byte<T: Counter>(block: T, byte: u8, bs: u8) -> Result<Self, OverflowError> {
+                fn from_block_byte<T: Counter>(block: T, byte: usize, bs: usize) -> Result<Self, OverflowError> {
                     debug_assert!(byte < bs);
                     let mut block: Self = block.try_into().map_err(|_| OverflowError)?;
                     if byte != 0 {
                         block -= 1;
                     }
-                    let pos = block.checked_mul(bs as Self).ok_or(OverflowError)? + (byte as Self);
+                    let bs = Self::try_from(bs).map_err(|_| OverflowError)?;
+                    let pos = block.checked_mul(bs).ok_or(OverflowError)? + (byte as Self);
                     Ok(pos)
                 }
 
-                fn into_block_byte<T: Counter>(self, bs: u8) -> Result<(T, u8), OverflowError> {
-                    let bs = bs as Self;
+                fn into_block_byte<T: Counter>(self, bs: usize) -> Result<(T, usize), OverflowError> {
+                    let bs = Self::try_from(bs).map_err(|_| OverflowError)?;
                     let byte = self % bs;
                     let block = T::try_from(self/bs).map_err(|_| OverflowError)?;
-                    Ok((block, byte as u8))
+                    Ok((block, byte as usize))
                 }
             }
         )*
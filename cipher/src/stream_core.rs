@@ -1,9 +1,8 @@
-use crate::{ParBlocks, ParBlocksSizeUser, StreamCipherError};
-use crypto_common::{
-    generic_array::{ArrayLength, GenericArray},
-    typenum::Unsigned,
-    Block, BlockSizeUser,
+use crate::{
+    utils::{into_chunks, xor_in2out, xor_par_in2out},
+    ParBlocks, ParBlocksSizeUser, StreamCipherError,
 };
+use crypto_common::{array::Array, Block, BlockSizeUser};
 use inout::{InOut, InOutBuf};
 
 /// Trait implemented by stream cipher backends.
@@ -14,16 +13,16 @@ pub trait StreamBackend: ParBlocksSizeUser {
     /// Generate keystream blocks in parallel.
     #[inline(always)]
     fn gen_par_ks_blocks(&mut self, blocks: &mut ParBlocks<Self>) {
-        for block in blocks {
+        for block in blocks.as_mut() {
             self.gen_ks_block(block);
         }
     }
 
     /// Generate keystream blocks. Length of the buffer MUST be smaller
-    /// than `Self::ParBlocksSize`.
+    /// than length of `Self::ParBlocks`.
     #[inline(always)]
     fn gen_tail_blocks(&mut self, blocks: &mut [Block<Self>]) {
-        assert!(blocks.len() < Self::ParBlocksSize::USIZE);
+        assert!(blocks.len() < ParBlocks::<Self>::LEN);
         for block in blocks {
             self.gen_ks_block(block);
         }
@@ -35,7 +34,7 @@ pub trait StreamBackend: ParBlocksSizeUser {
 /// This trait is used to define rank-2 closures.
 pub trait StreamClosure: BlockSizeUser {
     /// Execute closure with the provided stream cipher backend.
-    fn call<B: StreamBackend<BlockSize = Self::BlockSize>>(self, backend: &mut B);
+    fn call<B: StreamBackend<Block = Self::Block>>(self, backend: &mut B);
 }
 
 /// Block-level synchronous stream ciphers.
@@ -48,7 +47,7 @@ pub trait StreamCipherCore: BlockSizeUser + Sized {
     fn remaining_blocks(&self) -> Option<usize>;
 
     /// Process data using backend provided to the rank-2 closure.
-    fn process_with_backend(&mut self, f: impl StreamClosure<BlockSize = Self::BlockSize>);
+    fn process_with_backend(&mut self, f: impl StreamClosure<Block = Self::Block>);
 
     /// Write keystream block.
     ///
@@ -105,18 +104,19 @@ pub trait StreamCipherCore: BlockSizeUser + Sized {
         mut buf: InOutBuf<'_, '_, u8>,
     ) -> Result<(), StreamCipherError> {
         if let Some(rem) = self.remaining_blocks() {
-            let blocks = if buf.len() % Self::BlockSize::USIZE == 0 {
-                buf.len() % Self::BlockSize::USIZE
+            let bs = <Self::Block as Array>::LEN;
+            let blocks = if buf.len() % bs == 0 {
+                buf.len() / bs
             } else {
-                buf.len() % Self::BlockSize::USIZE + 1
+                buf.len() / bs + 1
             };
             if blocks > rem {
                 return Err(StreamCipherError);
             }
         }
 
-        if buf.len() > Self::BlockSize::USIZE {
-            let (blocks, tail) = buf.into_chunks();
+        if buf.len() > <Self::Block as Array>::LEN {
+            let (blocks, tail) = into_chunks::<_, Self::Block>(buf);
             self.apply_keystream_blocks_inout(blocks);
             buf = tail;
         }
@@ -124,11 +124,10 @@ pub trait StreamCipherCore: BlockSizeUser + Sized {
         if n == 0 {
             return Ok(());
         }
-        let mut block = Block::<Self>::default();
-        block[..n].copy_from_slice(buf.get_in());
-        let t = InOutBuf::from_mut(&mut block);
-        self.apply_keystream_blocks_inout(t);
-        buf.get_out().copy_from_slice(&block[..n]);
+        let mut block = Block::<Self>::zero();
+        block.as_mut()[..n].copy_from_slice(buf.get_in());
+        self.apply_keystream_block_inout(InOut::from(&mut block));
+        buf.get_out().copy_from_slice(&block.as_ref()[..n]);
         Ok(())
     }
 
@@ -190,51 +189,30 @@ macro_rules! impl_counter {
 
 impl_counter! { u32 u64 u128 }
 
-/// Partition buffer into 2 parts: buffer of arrays and tail.
-///
-/// In case if `N` is less or equal to 1, buffer of arrays has length
-/// of zero and tail is equal to `self`.
-#[inline]
-fn into_chunks<T, N: ArrayLength<T>>(buf: &mut [T]) -> (&mut [GenericArray<T, N>], &mut [T]) {
-    use core::slice;
-    if N::USIZE <= 1 {
-        return (&mut [], buf);
-    }
-    let chunks_len = buf.len() / N::USIZE;
-    let tail_pos = N::USIZE * chunks_len;
-    let tail_len = buf.len() - tail_pos;
-    unsafe {
-        let ptr = buf.as_mut_ptr();
-        let chunks = slice::from_raw_parts_mut(ptr as *mut GenericArray<T, N>, chunks_len);
-        let tail = slice::from_raw_parts_mut(ptr.add(tail_pos), tail_len);
-        (chunks, tail)
-    }
-}
-
-struct WriteBlockCtx<'a, BS: ArrayLength<u8>> {
-    block: &'a mut Block<Self>,
+struct WriteBlockCtx<'a, B: Array> {
+    block: &'a mut B,
 }
-impl<'a, BS: ArrayLength<u8>> BlockSizeUser for WriteBlockCtx<'a, BS> {
-    type BlockSize = BS;
+impl<'a, B: Array> BlockSizeUser for WriteBlockCtx<'a, B> {
+    type Block = B;
 }
-impl<'a, BS: ArrayLength<u8>> StreamClosure for WriteBlockCtx<'a, BS> {
+impl<'a, B: Array> StreamClosure for WriteBlockCtx<'a, B> {
     #[inline(always)]
-    fn call<B: StreamBackend<BlockSize = BS>>(self, backend: &mut B) {
+    fn call<Back: StreamBackend<Block = B>>(self, backend: &mut Back) {
         backend.gen_ks_block(self.block);
     }
 }
 
-struct WriteBlocksCtx<'a, BS: ArrayLength<u8>> {
-    blocks: &'a mut [Block<Self>],
+struct WriteBlocksCtx<'a, B: Array> {
+    blocks: &'a mut [B],
 }
-impl<'a, BS: ArrayLength<u8>> BlockSizeUser for WriteBlocksCtx<'a, BS> {
-    type BlockSize = BS;
+impl<'a, B: Array> BlockSizeUser for WriteBlocksCtx<'a, B> {
+    type Block = B;
 }
-impl<'a, BS: ArrayLength<u8>> StreamClosure for WriteBlocksCtx<'a, BS> {
+impl<'a, B: Array> StreamClosure for WriteBlocksCtx<'a, B> {
     #[inline(always)]
-    fn call<B: StreamBackend<BlockSize = BS>>(self, backend: &mut B) {
-        if B::ParBlocksSize::USIZE > 1 {
-            let (chunks, tail) = into_chunks::<_, B::ParBlocksSize>(self.blocks);
+    fn call<Back: StreamBackend<Block = B>>(self, backend: &mut Back) {
+        if ParBlocks::<Back>::LEN > 1 {
+            let (chunks, tail) = Back::ParBlocks::slice_as_chunks_mut(self.blocks);
             for chunk in chunks {
                 backend.gen_par_ks_blocks(chunk);
             }
@@ -247,54 +225,54 @@ impl<'a, BS: ArrayLength<u8>> StreamClosure for WriteBlocksCtx<'a, BS> {
     }
 }
 
-struct ApplyBlockCtx<'inp, 'out, BS: ArrayLength<u8>> {
-    block: InOut<'inp, 'out, Block<Self>>,
+struct ApplyBlockCtx<'inp, 'out, B: Array> {
+    block: InOut<'inp, 'out, B>,
 }
 
-impl<'inp, 'out, BS: ArrayLength<u8>> BlockSizeUser for ApplyBlockCtx<'inp, 'out, BS> {
-    type BlockSize = BS;
+impl<'inp, 'out, B: Array> BlockSizeUser for ApplyBlockCtx<'inp, 'out, B> {
+    type Block = B;
 }
 
-impl<'inp, 'out, BS: ArrayLength<u8>> StreamClosure for ApplyBlockCtx<'inp, 'out, BS> {
+impl<'inp, 'out, B: Array> StreamClosure for ApplyBlockCtx<'inp, 'out, B> {
     #[inline(always)]
-    fn call<B: StreamBackend<BlockSize = BS>>(mut self, backend: &mut B) {
-        let mut t = Default::default();
+    fn call<Back: StreamBackend<Block = B>>(mut self, backend: &mut Back) {
+        let mut t = B::zero();
         backend.gen_ks_block(&mut t);
-        self.block.xor_in2out(&t);
+        xor_in2out(&mut self.block, &t);
     }
 }
 
-struct ApplyBlocksCtx<'inp, 'out, BS: ArrayLength<u8>> {
-    blocks: InOutBuf<'inp, 'out, Block<Self>>,
+struct ApplyBlocksCtx<'inp, 'out, B: Array> {
+    blocks: InOutBuf<'inp, 'out, B>,
 }
 
-impl<'inp, 'out, BS: ArrayLength<u8>> BlockSizeUser for ApplyBlocksCtx<'inp, 'out, BS> {
-    type BlockSize = BS;
+impl<'inp, 'out, B: Array> BlockSizeUser for ApplyBlocksCtx<'inp, 'out, B> {
+    type Block = B;
 }
 
-impl<'inp, 'out, BS: ArrayLength<u8>> StreamClosure for ApplyBlocksCtx<'inp, 'out, BS> {
+impl<'inp, 'out, B: Array> StreamClosure for ApplyBlocksCtx<'inp, 'out, B> {
     #[inline(always)]
     #[allow(clippy::needless_range_loop)]
-    fn call<B: StreamBackend<BlockSize = BS>>(self, backend: &mut B) {
-        if B::ParBlocksSize::USIZE > 1 {
-            let (chunks, mut tail) = self.blocks.into_chunks::<B::ParBlocksSize>();
+    fn call<Back: StreamBackend<Block = B>>(self, backend: &mut Back) {
+        if ParBlocks::<Back>::LEN > 1 {
+            let (chunks, mut tail) = into_chunks::<_, Back::ParBlocks>(self.blocks);
             for mut chunk in chunks {
-                let mut tmp = Default::default();
+                let mut tmp = Back::ParBlocks::zero();
                 backend.gen_par_ks_blocks(&mut tmp);
-                chunk.xor_in2out(&tmp);
+                xor_par_in2out(&mut chunk, &tmp);
             }
             let n = tail.len();
-            let mut buf = GenericArray::<_, B::ParBlocksSize>::default();
-            let ks = &mut buf[..n];
+            let mut buf = Back::ParBlocks::zero();
+            let ks = &mut buf.as_mut()[..n];
             backend.gen_tail_blocks(ks);
             for i in 0..n {
-                tail.get(i).xor_in2out(&ks[i]);
+                xor_in2out(&mut tail.get(i), &ks[i]);
             }
         } else {
             for mut block in self.blocks {
-                let mut t = Default::default();
+                let mut t = B::zero();
                 backend.gen_ks_block(&mut t);
-                block.xor_in2out(&t);
+                xor_in2out(&mut block, &t);
             }
         }
     }
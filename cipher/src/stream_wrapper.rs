@@ -1,76 +1,60 @@
 use crate::{
-    errors::StreamCipherError, Block, OverflowError, SeekNum, StreamCipher, StreamCipherCore,
-    StreamCipherSeek, StreamCipherSeekCore,
+    errors::StreamCipherError, utils::into_chunks, Block, OverflowError, SeekNum, StreamCipher,
+    StreamCipherCore, StreamCipherSeek, StreamCipherSeekCore,
 };
-use crypto_common::{
-    typenum::{IsLess, Le, NonZero, Unsigned, U256},
-    BlockSizeUser, Iv, IvSizeUser, Key, KeyInit, KeyIvInit, KeySizeUser,
-};
-use inout::InOutBuf;
 #[cfg(feature = "zeroize")]
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use block_buffer::zeroize::ZeroizeOnDrop;
+use block_buffer::ReadBuffer;
+use crypto_common::{array::Array, BlockSizeUser, InnerInit, InnerIv, InnerUser};
+use inout::InOutBuf;
 
 /// Wrapper around [`StreamCipherCore`] implementations.
 ///
 /// It handles data buffering and implements the slice-based traits.
-#[derive(Clone, Default)]
-pub struct StreamCipherCoreWrapper<T: BlockSizeUser>
-where
-    T::BlockSize: IsLess<U256>,
-    Le<T::BlockSize, U256>: NonZero,
-{
+/// Cursor of the buffer is stored as [`u16`], so blocks of up to
+/// 65536 bytes are supported.
+pub struct StreamCipherCoreWrapper<T: BlockSizeUser> {
     core: T,
-    buffer: Block<T>,
-    pos: u8,
+    buffer: ReadBuffer<Block<T>, u16>,
 }
 
-impl<T: StreamCipherCore> StreamCipherCoreWrapper<T>
-where
-    T::BlockSize: IsLess<U256>,
-    Le<T::BlockSize, U256>: NonZero,
-{
-    /// Return reference to the core type.
-    pub fn get_core(&self) -> &T {
-        &self.core
-    }
-
-    /// Return reference to the core type.
-    pub fn from_core(core: T) -> Self {
+impl<T: BlockSizeUser + Clone> Clone for StreamCipherCoreWrapper<T> {
+    #[inline]
+    fn clone(&self) -> Self {
         Self {
-            core,
-            buffer: Default::default(),
-            pos: 0,
+            core: self.core.clone(),
+            buffer: self.buffer.clone(),
         }
     }
+}
 
-    /// Return current cursor position.
+impl<T: BlockSizeUser + Default> Default for StreamCipherCoreWrapper<T> {
     #[inline]
-    fn get_pos(&self) -> usize {
-        let pos = self.pos as usize;
-        if pos >= T::BlockSize::USIZE {
-            debug_assert!(false);
-            // SAFETY: `pos` is set only to values smaller than block size
-            unsafe { core::hint::unreachable_unchecked() }
-        }
-        self.pos as usize
+    fn default() -> Self {
+        Self::wrap(T::default())
     }
+}
 
-    /// Return size of the internall buffer in bytes.
+impl<T: BlockSizeUser> StreamCipherCoreWrapper<T> {
+    /// Wrap `core` with an empty keystream buffer.
     #[inline]
-    fn size(&self) -> usize {
-        T::BlockSize::USIZE
+    fn wrap(core: T) -> Self {
+        Self {
+            core,
+            buffer: Default::default(),
+        }
     }
+}
 
-    #[inline]
-    fn set_pos_unchecked(&mut self, pos: usize) {
-        debug_assert!(pos < T::BlockSize::USIZE);
-        self.pos = pos as u8;
+impl<T: StreamCipherCore> StreamCipherCoreWrapper<T> {
+    /// Return reference to the core type.
+    pub fn get_core(&self) -> &T {
+        &self.core
     }
 
-    /// Return number of remaining bytes in the internall buffer.
-    #[inline]
-    fn remaining(&self) -> usize {
-        self.size() - self.get_pos()
+    /// Return reference to the core type.
+    pub fn from_core(core: T) -> Self {
+        Self::wrap(core)
     }
 
     fn check_remaining(&self, dlen: usize) -> Result<(), StreamCipherError> {
@@ -79,17 +63,13 @@ where
             None => return Ok(()),
         };
 
-        let bytes = if self.pos == 0 {
-            dlen
+        let rem = self.buffer.remaining();
+        let bytes = if dlen > rem {
+            dlen - rem
         } else {
-            let rem = self.remaining();
-            if dlen > rem {
-                dlen - rem
-            } else {
-                return Ok(());
-            }
+            return Ok(());
         };
-        let bs = T::BlockSize::USIZE;
+        let bs = T::Block::LEN;
         let blocks = if bytes % bs == 0 {
             bytes / bs
         } else {
@@ -103,137 +83,67 @@ where
     }
 }
 
-impl<T: StreamCipherCore> StreamCipher for StreamCipherCoreWrapper<T>
-where
-    T::BlockSize: IsLess<U256>,
-    Le<T::BlockSize, U256>: NonZero,
-{
+impl<T: StreamCipherCore> StreamCipher for StreamCipherCoreWrapper<T> {
     #[inline]
     fn try_apply_keystream_inout(
         &mut self,
-        mut data: InOutBuf<'_, '_, u8>,
+        data: InOutBuf<'_, '_, u8>,
     ) -> Result<(), StreamCipherError> {
         self.check_remaining(data.len())?;
 
-        let pos = self.get_pos();
-        if pos != 0 {
-            let rem = &self.buffer[pos..];
-            let n = data.len();
-            if n < rem.len() {
-                data.xor_in2out(&rem[..n]);
-                self.set_pos_unchecked(pos + n);
-                return Ok(());
-            }
-            let (mut left, right) = data.split_at(rem.len());
-            data = right;
-            left.xor_in2out(rem);
-        }
+        let Self { core, buffer } = self;
+        let head_ks = buffer.read_cached(data.len());
+        let (mut head, data) = data.split_at(head_ks.len());
+        head.xor_in2out(head_ks);
 
-        let (blocks, mut leftover) = data.into_chunks();
-        self.core.apply_keystream_blocks_inout(blocks);
+        let (blocks, mut tail) = into_chunks::<_, Block<T>>(data);
+        core.apply_keystream_blocks_inout(blocks);
 
-        let n = leftover.len();
+        let n = tail.len();
         if n != 0 {
-            self.core.write_keystream_block(&mut self.buffer);
-            leftover.xor_in2out(&self.buffer[..n]);
+            buffer.write_block(
+                n,
+                |block| core.write_keystream_block(block),
+                |tail_ks| tail.xor_in2out(tail_ks),
+            );
         }
-        self.set_pos_unchecked(n);
 
         Ok(())
     }
 }
 
-impl<T: StreamCipherSeekCore> StreamCipherSeek for StreamCipherCoreWrapper<T>
-where
-    T::BlockSize: IsLess<U256>,
-    Le<T::BlockSize, U256>: NonZero,
-{
+impl<T: StreamCipherSeekCore> StreamCipherSeek for StreamCipherCoreWrapper<T> {
     fn try_current_pos<SN: SeekNum>(&self) -> Result<SN, OverflowError> {
-        let Self { core, pos, .. } = self;
-        SN::from_block_byte(core.get_block_pos(), *pos, T::BlockSize::U8)
+        let Self { core, buffer } = self;
+        SN::from_block_byte(core.get_block_pos(), buffer.get_pos(), T::Block::LEN)
     }
 
     fn try_seek<SN: SeekNum>(&mut self, new_pos: SN) -> Result<(), StreamCipherError> {
-        let Self { core, buffer, pos } = self;
-        let (block_pos, byte_pos) = new_pos.into_block_byte(T::BlockSize::U8)?;
+        let Self { core, buffer } = self;
+        let (block_pos, byte_pos) = new_pos.into_block_byte(T::Block::LEN)?;
         core.set_block_pos(block_pos);
         if byte_pos != 0 {
-            self.core.write_keystream_block(buffer);
+            buffer.write_block(byte_pos, |block| core.write_keystream_block(block), |_| {});
+        } else {
+            buffer.reset();
         }
-        *pos = byte_pos;
         Ok(())
     }
 }
 
-// Note: ideally we would only implement the InitInner trait and everything
-// else would be handled by blanket impls, but unfortunately it will
-// not work properly without mutually exclusive traits, see:
-// https://github.com/rust-lang/rfcs/issues/1053
-
-impl<T: KeySizeUser + BlockSizeUser> KeySizeUser for StreamCipherCoreWrapper<T>
-where
-    T::BlockSize: IsLess<U256>,
-    Le<T::BlockSize, U256>: NonZero,
-{
-    type KeySize = T::KeySize;
+// Key and IV sizes and the `KeyInit` and `KeyIvInit` traits
+// are implemented by the `crypto-common` blanket impls
+impl<T: BlockSizeUser> InnerUser for StreamCipherCoreWrapper<T> {
+    type Inner = T;
+    type IvMode = InnerIv;
 }
 
-impl<T: IvSizeUser + BlockSizeUser> IvSizeUser for StreamCipherCoreWrapper<T>
-where
-    T::BlockSize: IsLess<U256>,
-    Le<T::BlockSize, U256>: NonZero,
-{
-    type IvSize = T::IvSize;
-}
-
-impl<T: KeyIvInit + BlockSizeUser> KeyIvInit for StreamCipherCoreWrapper<T>
-where
-    T::BlockSize: IsLess<U256>,
-    Le<T::BlockSize, U256>: NonZero,
-{
+impl<T: BlockSizeUser> InnerInit for StreamCipherCoreWrapper<T> {
     #[inline]
-    fn new(key: &Key<Self>, iv: &Iv<Self>) -> Self {
-        Self {
-            core: T::new(key, iv),
-            buffer: Default::default(),
-            pos: 0,
-        }
-    }
-}
-
-impl<T: KeyInit + BlockSizeUser> KeyInit for StreamCipherCoreWrapper<T>
-where
-    T::BlockSize: IsLess<U256>,
-    Le<T::BlockSize, U256>: NonZero,
-{
-    #[inline]
-    fn new(key: &Key<Self>) -> Self {
-        Self {
-            core: T::new(key),
-            buffer: Default::default(),
-            pos: 0,
-        }
+    fn inner_init(core: T) -> Self {
+        Self::wrap(core)
     }
 }
 
 #[cfg(feature = "zeroize")]
-impl<T> Drop for StreamCipherCoreWrapper<T>
-where
-    T: BlockSizeUser,
-    T::BlockSize: IsLess<U256>,
-    Le<T::BlockSize, U256>: NonZero,
-{
-    fn drop(&mut self) {
-        self.buffer.zeroize();
-        self.pos.zeroize();
-    }
-}
-
-#[cfg(feature = "zeroize")]
-impl<T> ZeroizeOnDrop for StreamCipherCoreWrapper<T>
-where
-    T: BlockSizeUser + ZeroizeOnDrop,
-    T::BlockSize: IsLess<U256>,
-    Le<T::BlockSize, U256>: NonZero,
-{
-}
+impl<T> ZeroizeOnDrop for StreamCipherCoreWrapper<T> where T: BlockSizeUser + ZeroizeOnDrop {}
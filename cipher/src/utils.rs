@@ -0,0 +1,188 @@
+//! Helper functions for working with `inout` buffers of [`Array`] blocks.
+
+use crate::array::{Array, Zero};
+use inout::{InOut, InOutBuf};
+
+#[cfg(feature = "block-padding")]
+use inout::{
+    block_padding::{PadType, RawPadding, UnpadError},
+    InOutBufReserved, PadError,
+};
+
+/// Partition buffer into 2 parts: buffer of arrays and tail.
+///
+/// In case if `A::LEN` is equal to 0, buffer of arrays has length
+/// of zero and tail is equal to `buf`.
+#[inline(always)]
+pub(crate) fn into_chunks<'inp, 'out, T: Zero, A: Array<T>>(
+    buf: InOutBuf<'inp, 'out, T>,
+) -> (InOutBuf<'inp, 'out, A>, InOutBuf<'inp, 'out, T>) {
+    if A::LEN == 0 {
+        let (empty, tail) = buf.split_at(0);
+        let (in_ptr, out_ptr) = empty.into_raw();
+        // SAFETY: zero-length buffer is always valid
+        let chunks = unsafe { InOutBuf::from_raw(in_ptr as *const A, out_ptr as *mut A, 0) };
+        return (chunks, tail);
+    }
+    let n = buf.len() / A::LEN;
+    let (chunks, tail) = buf.split_at(n * A::LEN);
+    let (in_ptr, out_ptr) = chunks.into_raw();
+    // SAFETY: `chunks` is valid for `n * A::LEN` elements and `Array`
+    // guarantees that `A` has the same layout as `[T; A::LEN]`
+    let chunks = unsafe { InOutBuf::from_raw(in_ptr as *const A, out_ptr as *mut A, n) };
+    (chunks, tail)
+}
+
+/// Convert inout array into inout buffer of its elements.
+#[inline(always)]
+pub(crate) fn array_into_buf<'inp, 'out, T: Zero, A: Array<T>>(
+    arr: InOut<'inp, 'out, A>,
+) -> InOutBuf<'inp, 'out, T> {
+    let (in_ptr, out_ptr) = arr.into_raw();
+    // SAFETY: `Array` guarantees that `A` has the same layout as `[T; A::LEN]`
+    unsafe { InOutBuf::from_raw(in_ptr as *const T, out_ptr as *mut T, A::LEN) }
+}
+
+/// XOR `data` with value behind the input pointer of `block` and write
+/// result to the output pointer.
+#[inline(always)]
+pub(crate) fn xor_in2out<B: Array>(block: &mut InOut<'_, '_, B>, data: &B) {
+    let mut t = block.clone_in();
+    xor(&mut t, data);
+    *block.get_out() = t;
+}
+
+/// XOR `data` with values behind the input pointer of `blocks` and write
+/// result to the output pointer.
+#[inline(always)]
+pub(crate) fn xor_par_in2out<B: Array, P: Array<B>>(blocks: &mut InOut<'_, '_, P>, data: &P) {
+    let mut t = blocks.clone_in();
+    for (a, b) in t.as_mut().iter_mut().zip(data.as_ref()) {
+        xor(a, b);
+    }
+    *blocks.get_out() = t;
+}
+
+#[inline(always)]
+fn xor<B: Array>(a: &mut B, b: &B) {
+    for (a, b) in a.as_mut().iter_mut().zip(b.as_ref()) {
+        *a ^= *b;
+    }
+}
+
+/// Variant of [`InOutBuf`] with optional padded tail block.
+#[cfg(feature = "block-padding")]
+pub(crate) struct PaddedInOutBuf<'inp, 'out, B: Array> {
+    blocks: InOutBuf<'inp, 'out, B>,
+    tail_in: B,
+    tail_out: Option<&'out mut B>,
+}
+
+#[cfg(feature = "block-padding")]
+impl<'inp, 'out, B: Array> PaddedInOutBuf<'inp, 'out, B> {
+    /// Transform buffer into [`PaddedInOutBuf`] using padding algorithm `P`.
+    #[inline(always)]
+    pub(crate) fn new<P: RawPadding>(
+        mut data: InOutBufReserved<'inp, 'out, u8>,
+    ) -> Result<Self, PadError> {
+        let in_len = data.get_in().len();
+        let in_ptr = data.get_in().as_ptr();
+        let out_len = data.get_out().len();
+        let out_ptr = data.get_out().as_mut_ptr();
+
+        let bs = B::LEN;
+        let blocks_len = in_len / bs;
+        let tail_len = in_len - bs * blocks_len;
+        // SAFETY: `in_ptr..in_ptr + bs * blocks_len` is valid region for reads
+        // and `out_ptr..out_ptr + bs * blocks_len` is valid region for writes
+        // since `out_len` is bigger or equal to `in_len`. `Array` guarantees
+        // that `B` has the same layout as `[u8; bs]`, i.e. alignment of 1
+        let blocks =
+            unsafe { InOutBuf::from_raw(in_ptr as *const B, out_ptr as *mut B, blocks_len) };
+        let mut tail_in = B::zero();
+        let tail_out = match P::TYPE {
+            PadType::NoPadding | PadType::Ambiguous if tail_len == 0 => None,
+            PadType::NoPadding => return Err(PadError),
+            PadType::Reversible | PadType::Ambiguous => {
+                let blen = bs * blocks_len;
+                let res_len = blen + bs;
+                if res_len > out_len {
+                    return Err(PadError);
+                }
+                // SAFETY: `in_ptr + blen..in_ptr + blen + tail_len`
+                // is valid region for reads and `tail_len` is smaller than `bs`.
+                // we have verified that `blen + bs <= out_len`, in other words,
+                // `out_ptr + blen..out_ptr + blen + bs` is valid region
+                // for writes and `B` has alignment of 1.
+                let out_block = unsafe {
+                    core::ptr::copy_nonoverlapping(
+                        in_ptr.add(blen),
+                        tail_in.as_mut().as_mut_ptr(),
+                        tail_len,
+                    );
+                    &mut *(out_ptr.add(blen) as *mut B)
+                };
+                P::raw_pad(tail_in.as_mut(), tail_len);
+                Some(out_block)
+            }
+        };
+        Ok(Self {
+            blocks,
+            tail_in,
+            tail_out,
+        })
+    }
+
+    /// Get full blocks.
+    #[inline(always)]
+    pub(crate) fn get_blocks(&mut self) -> InOutBuf<'_, '_, B> {
+        self.blocks.reborrow()
+    }
+
+    /// Get padded tail block.
+    ///
+    /// For paddings with `P::TYPE = PadType::Reversible` it always returns `Some`.
+    #[inline(always)]
+    pub(crate) fn get_tail_block(&mut self) -> Option<InOut<'_, '_, B>> {
+        match self.tail_out.as_deref_mut() {
+            Some(out_block) => Some((&self.tail_in, out_block).into()),
+            None => None,
+        }
+    }
+
+    /// Convert buffer into output slice.
+    #[inline(always)]
+    pub(crate) fn into_out(self) -> &'out [u8] {
+        let total_blocks = if self.tail_out.is_some() {
+            self.blocks.len() + 1
+        } else {
+            self.blocks.len()
+        };
+        let res_len = B::LEN * total_blocks;
+        let (_, out_ptr) = self.blocks.into_raw();
+        // SAFETY: `res_len` is always valid for the output buffer since
+        // it's checked during type construction
+        unsafe { core::slice::from_raw_parts(out_ptr as *const u8, res_len) }
+    }
+}
+
+/// Unpad data in the `blocks` using padding algorithm `P`.
+///
+/// Returns `Err(UnpadError)` if the last block contains malformed padding.
+#[cfg(feature = "block-padding")]
+#[inline(always)]
+pub(crate) fn unpad_blocks<P: RawPadding, B: Array>(blocks: &[B]) -> Result<&[u8], UnpadError> {
+    let bs = B::LEN;
+    let res_len = match (blocks.last(), P::TYPE) {
+        (_, PadType::NoPadding) => bs * blocks.len(),
+        (Some(last_block), _) => {
+            let n = P::raw_unpad(last_block.as_ref())?.len();
+            assert!(n <= bs);
+            n + bs * (blocks.len() - 1)
+        }
+        (None, PadType::Ambiguous) => 0,
+        (None, PadType::Reversible) => return Err(UnpadError),
+    };
+    // SAFETY: `res_len` is always smaller or equal to `bs * blocks.len()`
+    Ok(unsafe { core::slice::from_raw_parts(blocks.as_ptr() as *const u8, res_len) })
+}
@@ -0,0 +1,165 @@
+//! Smoke tests for the `dev` macros over toy `[u8; N]`-based ciphers.
+#![cfg(feature = "dev")]
+
+use cipher::{
+    Block, BlockSizeUser, IvSizeUser, KeyInit, KeyIvInit, KeySizeUser, ParBlocksSizeUser,
+    StreamBackend, StreamCipherCore, StreamCipherCoreWrapper, StreamCipherSeekCore, StreamClosure,
+};
+
+/// Toy block cipher: XOR with the key followed by a bit rotation.
+#[derive(Clone)]
+struct ToyBlock {
+    key: [u8; 8],
+}
+
+impl KeySizeUser for ToyBlock {
+    type Key = [u8; 8];
+}
+
+impl KeyInit for ToyBlock {
+    fn new(key: [u8; 8]) -> Self {
+        Self { key }
+    }
+}
+
+impl ToyBlock {
+    fn encrypt(&self, block: &mut [u8; 8]) {
+        for (b, k) in block.iter_mut().zip(self.key.iter()) {
+            *b = (*b ^ k).rotate_left(3);
+        }
+    }
+}
+
+cipher::impl_simple_block_encdec!(
+    ToyBlock, [u8; 8], state, block,
+    encrypt: {
+        let mut b = *block.get_in();
+        state.encrypt(&mut b);
+        *block.get_out() = b;
+    }
+    decrypt: {
+        let mut b = *block.get_in();
+        for (b, k) in b.iter_mut().zip(state.key.iter()) {
+            *b = b.rotate_right(3) ^ k;
+        }
+        *block.get_out() = b;
+    }
+);
+
+cipher::block_cipher_test!(toy_block, "toy_block", ToyBlock);
+
+/// Toy CTR-mode core: keystream block `i` is `ToyBlock(key)` applied
+/// to `iv ^ i.to_le_bytes()`.
+struct ToyCtrCore {
+    cipher: ToyBlock,
+    iv: [u8; 8],
+    ctr: u64,
+}
+
+impl BlockSizeUser for ToyCtrCore {
+    type Block = [u8; 8];
+}
+
+impl KeySizeUser for ToyCtrCore {
+    type Key = [u8; 8];
+}
+
+impl IvSizeUser for ToyCtrCore {
+    type Iv = [u8; 8];
+}
+
+impl KeyIvInit for ToyCtrCore {
+    fn new(key: [u8; 8], iv: [u8; 8]) -> Self {
+        Self {
+            cipher: ToyBlock::new(key),
+            iv,
+            ctr: 0,
+        }
+    }
+}
+
+struct Backend<'a>(&'a mut ToyCtrCore);
+
+impl<'a> BlockSizeUser for Backend<'a> {
+    type Block = [u8; 8];
+}
+
+impl<'a> ParBlocksSizeUser for Backend<'a> {
+    type ParBlocks = [[u8; 8]; 1];
+}
+
+impl<'a> StreamBackend for Backend<'a> {
+    fn gen_ks_block(&mut self, block: &mut Block<Self>) {
+        let ctr = self.0.ctr.to_le_bytes();
+        for ((b, iv), c) in block.iter_mut().zip(self.0.iv.iter()).zip(ctr.iter()) {
+            *b = iv ^ c;
+        }
+        self.0.cipher.encrypt(block);
+        self.0.ctr = self.0.ctr.wrapping_add(1);
+    }
+}
+
+impl StreamCipherCore for ToyCtrCore {
+    fn remaining_blocks(&self) -> Option<usize> {
+        None
+    }
+
+    fn process_with_backend(&mut self, f: impl StreamClosure<Block = Self::Block>) {
+        f.call(&mut Backend(self));
+    }
+}
+
+impl StreamCipherSeekCore for ToyCtrCore {
+    type Counter = u64;
+
+    fn get_block_pos(&self) -> u64 {
+        self.ctr
+    }
+
+    fn set_block_pos(&mut self, pos: u64) {
+        self.ctr = pos;
+    }
+}
+
+type ToyCtr = StreamCipherCoreWrapper<ToyCtrCore>;
+
+cipher::stream_cipher_test!(toy_ctr, "toy_ctr", ToyCtr);
+cipher::stream_cipher_seek_test!(toy_ctr_seek, ToyCtr);
+
+fn keystream(len: usize) -> Vec<u8> {
+    let mut core = ToyCtrCore::new([1; 8], [2; 8]);
+    let mut ks = vec![0u8; (len + 7) / 8 * 8];
+    for block in ks.chunks_mut(8) {
+        let mut b = [0u8; 8];
+        core.write_keystream_block(&mut b);
+        block.copy_from_slice(&b);
+    }
+    ks.truncate(len);
+    ks
+}
+
+#[test]
+fn toy_ctr_partial_blocks() {
+    use cipher::{StreamCipher, StreamCipherSeek};
+
+    let expected = keystream(45);
+    for &chunk in &[1, 3, 7, 8, 9, 16, 45] {
+        let mut c = ToyCtr::new([1; 8], [2; 8]);
+        let mut buf = [0u8; 45];
+        for (i, part) in buf.chunks_mut(chunk).enumerate() {
+            c.apply_keystream(part);
+            let pos: u64 = c.current_pos();
+            assert_eq!(pos as usize, core::cmp::min((i + 1) * chunk, 45));
+        }
+        assert_eq!(buf[..], expected[..], "chunk {}", chunk);
+    }
+
+    // seeking into the middle of a block caches its remaining keystream
+    let mut c = ToyCtr::new([1; 8], [2; 8]);
+    c.seek(13u64);
+    let mut buf = [0u8; 20];
+    c.apply_keystream(&mut buf[..2]);
+    c.apply_keystream(&mut buf[2..]);
+    assert_eq!(buf[..], expected[13..33]);
+    assert_eq!(c.current_pos::<u64>(), 33);
+}
@@ -0,0 +1,75 @@
+//! Error types.
+
+use crate::Lengths;
+use core::fmt;
+
+/// Parameter which had an invalid length.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum LengthParam {
+    /// Key.
+    Key,
+    /// Initialization vector (nonce).
+    Iv,
+}
+
+impl fmt::Display for LengthParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Key => "key",
+            Self::Iv => "IV",
+        })
+    }
+}
+
+/// The error type returned when key and/or IV used in the [`KeyInit`],
+/// [`KeyIvInit`], and [`InnerIvInit`] slice-based methods had
+/// an invalid length.
+///
+/// [`KeyInit`]: crate::KeyInit
+/// [`KeyIvInit`]: crate::KeyIvInit
+/// [`InnerIvInit`]: crate::InnerIvInit
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct InvalidLength {
+    /// Parameter which had an invalid length.
+    pub param: LengthParam,
+    /// Lengths supported for the parameter.
+    pub expected: Lengths,
+    /// Length of the provided parameter.
+    pub actual: usize,
+}
+
+impl InvalidLength {
+    /// Create error for a key with length `actual`.
+    #[inline]
+    pub const fn key(expected: Lengths, actual: usize) -> Self {
+        Self {
+            param: LengthParam::Key,
+            expected,
+            actual,
+        }
+    }
+
+    /// Create error for an IV with length `actual`.
+    #[inline]
+    pub const fn iv(expected: usize, actual: usize) -> Self {
+        Self {
+            param: LengthParam::Iv,
+            expected: Lengths::fixed(expected),
+            actual,
+        }
+    }
+}
+
+impl fmt::Display for InvalidLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid {} length: expected {}, got {}",
+            self.param, self.expected, self.actual
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for InvalidLength {}
@@ -16,17 +16,39 @@
 #[cfg(feature = "std")]
 extern crate std;
 
-use array::Array;
+pub use array;
 #[cfg(feature = "rand_core")]
 pub use rand_core;
+#[cfg(feature = "secret")]
+pub use subtle;
+#[cfg(feature = "secret")]
+pub use zeroize;
+
+mod errors;
+#[cfg(feature = "secret")]
+mod secret;
+
+pub use errors::{InvalidLength, LengthParam};
+#[cfg(feature = "secret")]
+#[cfg_attr(docsrs, doc(cfg(feature = "secret")))]
+pub use secret::SecretKey;
+
+use array::Array;
 
-use core::{
-    array::TryFromSliceError,
-    convert::{TryFrom, TryInto},
-    fmt,
-};
+use core::{fmt, marker::PhantomData};
 #[cfg(feature = "rand_core")]
 use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "secret")]
+use zeroize::Zeroize;
+
+/// Block on which [`BlockSizeUser`] implementors operate.
+pub type Block<B> = <B as BlockSizeUser>::Block;
+/// Output array of [`OutputSizeUser`] implementors.
+pub type Output<T> = <T as OutputSizeUser>::Output;
+/// Key used by [`KeySizeUser`] implementors.
+pub type Key<B> = <B as KeySizeUser>::Key;
+/// Initialization vector (nonce) used by [`IvSizeUser`] implementors.
+pub type Iv<B> = <B as IvSizeUser>::Iv;
 
 /// Types which process data in blocks.
 pub trait BlockSizeUser {
@@ -54,6 +76,177 @@ pub trait OutputSizeUser {
 pub trait KeySizeUser {
     /// Size of the block in bytes.
     type Key: Array;
+
+    /// Key lengths accepted by slice constructors, e.g. [`KeyInit::new_from_slice`].
+    ///
+    /// By default only keys with length equal to `Key::LEN` are accepted.
+    const KEY_SIZES: Lengths = Lengths::fixed(<Self::Key as Array>::LEN);
+}
+
+/// Set of supported lengths of a parameter such as a key or an IV.
+///
+/// It's used for [`KeySizeUser::KEY_SIZES`] and reported as expected lengths
+/// in [`InvalidLength`] errors for both keys and IVs.
+///
+/// Algorithms which accept keys of lengths other than `Key::LEN` declare
+/// them using [`KeySizeUser::KEY_SIZES`] and override slice constructors
+/// such as [`KeyInit::new_from_slice`]:
+///
+/// ```
+/// use crypto_common::{InvalidLength, KeyInit, KeySizeUser, Lengths};
+///
+/// struct Rc4 {
+///     key: [u8; 256],
+///     len: usize,
+/// }
+///
+/// impl KeySizeUser for Rc4 {
+///     type Key = [u8; 16];
+///     const KEY_SIZES: Lengths = Lengths::range(1, 256, 1);
+/// }
+///
+/// impl KeyInit for Rc4 {
+///     fn new(key: [u8; 16]) -> Self {
+///         Self::new_from_slice(&key).unwrap()
+///     }
+///
+///     fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+///         Self::KEY_SIZES.check_key(key.len())?;
+///         let mut buf = [0u8; 256];
+///         buf[..key.len()].copy_from_slice(key);
+///         Ok(Self { key: buf, len: key.len() })
+///     }
+/// }
+///
+/// assert!(Rc4::new_from_slice(&[0u8; 5]).is_ok());
+/// assert!(Rc4::new_from_slice(&[]).is_err());
+/// assert!(Rc4::new_from_slice(&[0u8; 257]).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Lengths {
+    /// Lengths from `min` to `max` (inclusive) in increments of `step`.
+    Range {
+        /// Minimum length.
+        min: usize,
+        /// Maximum length.
+        max: usize,
+        /// Difference between consecutive supported lengths.
+        step: usize,
+    },
+    /// Explicit set of supported lengths.
+    Set(&'static [usize]),
+}
+
+impl Lengths {
+    /// Any length, e.g. used for HMAC keys.
+    pub const ANY: Self = Self::range(0, usize::MAX, 1);
+
+    /// Exactly `len` bytes.
+    #[inline]
+    pub const fn fixed(len: usize) -> Self {
+        Self::range(len, len, 1)
+    }
+
+    /// Lengths from `min` to `max` (inclusive) in increments of `step`.
+    #[inline]
+    pub const fn range(min: usize, max: usize, step: usize) -> Self {
+        Self::Range { min, max, step }
+    }
+
+    /// Check whether `len` is supported.
+    pub const fn contains(&self, len: usize) -> bool {
+        match *self {
+            Self::Range { min, max, step } => {
+                if len < min || len > max {
+                    false
+                } else if step == 0 {
+                    len == min
+                } else {
+                    (len - min) % step == 0
+                }
+            }
+            Self::Set(lens) => {
+                let mut i = 0;
+                while i < lens.len() {
+                    if lens[i] == len {
+                        return true;
+                    }
+                    i += 1;
+                }
+                false
+            }
+        }
+    }
+
+    /// Check whether `len` is the only supported length.
+    const fn is_fixed(&self, len: usize) -> bool {
+        match *self {
+            Self::Range { min, max, .. } => min == len && max == len,
+            Self::Set(lens) => {
+                let mut i = 0;
+                while i < lens.len() {
+                    if lens[i] != len {
+                        return false;
+                    }
+                    i += 1;
+                }
+                !lens.is_empty()
+            }
+        }
+    }
+
+    /// Return a key length error if `len` is not supported.
+    #[inline]
+    pub fn check_key(&self, len: usize) -> Result<(), InvalidLength> {
+        if self.contains(len) {
+            Ok(())
+        } else {
+            Err(InvalidLength::key(*self, len))
+        }
+    }
+}
+
+impl fmt::Display for Lengths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Range { min, max, .. } if min == max => write!(f, "{}", min),
+            Self::Range { min, max, step: 1 } => write!(f, "{}..={}", min, max),
+            Self::Range { min, max, step } => write!(f, "{}..={} (step {})", min, max, step),
+            Self::Set(lens) => write!(f, "one of {:?}", lens),
+        }
+    }
+}
+
+/// Copy key from `key`, which must have length equal to `K::LEN`.
+///
+/// `expected` is reported in the returned error.
+#[inline]
+pub(crate) fn copy_key<K: Array>(key: &[u8], expected: Lengths) -> Result<K, InvalidLength> {
+    K::try_copy_from_slice(key).map_err(|_| InvalidLength::key(expected, key.len()))
+}
+
+/// Compile-time check used by the default slice constructors, which can only
+/// handle keys of exactly `Key::LEN` bytes.
+struct FixedKeySize<T>(PhantomData<T>);
+
+impl<T: KeySizeUser> FixedKeySize<T> {
+    const CHECK: () = assert!(
+        T::KEY_SIZES.is_fixed(<T::Key as Array>::LEN),
+        "key initialization from slice must be overridden for variable key sizes"
+    );
+
+    #[inline(always)]
+    #[allow(clippy::let_unit_value)]
+    fn copy_key(key: &[u8]) -> Result<T::Key, InvalidLength> {
+        let () = Self::CHECK;
+        copy_key(key, T::KEY_SIZES)
+    }
+}
+
+/// Copy IV from `iv`, which must have length equal to `I::LEN`.
+#[inline]
+fn copy_iv<I: Array>(iv: &[u8]) -> Result<I, InvalidLength> {
+    I::try_copy_from_slice(iv).map_err(|_| InvalidLength::iv(I::LEN, iv.len()))
 }
 
 /// Types which use initialization vector (nonce) for initialization.
@@ -67,9 +260,107 @@ pub trait IvSizeUser {
 /// Types which use another type for initialization.
 ///
 /// Generally it's used indirectly via [`InnerInit`] or [`InnerIvInit`].
+///
+/// [`KeyIvInit`] is implemented automatically depending on [`InnerUser::IvMode`]:
+/// types with [`WrapperIv`] initialize the inner type from key and consume IV
+/// using [`InnerIvInit`] (e.g. block modes over block ciphers), while types
+/// with [`InnerIv`] pass both key and IV to the inner type and wrap it using
+/// [`InnerInit`] (e.g. wrappers around stream cipher cores).
+///
+/// ```
+/// use crypto_common::{
+///     InnerInit, InnerIv, InnerIvInit, InnerUser, IvSizeUser, KeyInit, KeyIvInit,
+///     KeySizeUser, WrapperIv,
+/// };
+///
+/// struct Cipher([u8; 16]);
+///
+/// impl KeySizeUser for Cipher {
+///     type Key = [u8; 16];
+/// }
+///
+/// impl KeyInit for Cipher {
+///     fn new(key: [u8; 16]) -> Self {
+///         Self(key)
+///     }
+/// }
+///
+/// // inner takes key, wrapper takes IV
+/// struct Mode {
+///     cipher: Cipher,
+///     iv: [u8; 8],
+/// }
+///
+/// impl InnerUser for Mode {
+///     type Inner = Cipher;
+///     type IvMode = WrapperIv;
+/// }
+///
+/// impl IvSizeUser for Mode {
+///     type Iv = [u8; 8];
+/// }
+///
+/// impl InnerIvInit for Mode {
+///     fn inner_iv_init(cipher: Cipher, iv: [u8; 8]) -> Self {
+///         Self { cipher, iv }
+///     }
+/// }
+///
+/// // inner takes key and IV
+/// struct Wrapper(Mode);
+///
+/// impl InnerUser for Wrapper {
+///     type Inner = Mode;
+///     type IvMode = InnerIv;
+/// }
+///
+/// impl InnerInit for Wrapper {
+///     fn inner_init(mode: Mode) -> Self {
+///         Self(mode)
+///     }
+/// }
+///
+/// let mode = Mode::new_from_slices(&[1; 16], &[2; 8]).unwrap();
+/// assert_eq!((mode.cipher.0, mode.iv), ([1; 16], [2; 8]));
+/// let wrapper = Wrapper::new([1; 16], [2; 8]);
+/// assert_eq!((wrapper.0.cipher.0, wrapper.0.iv), ([1; 16], [2; 8]));
+/// assert!(Wrapper::new_from_slices(&[1; 16], &[2; 7]).is_err());
+/// ```
 pub trait InnerUser {
     /// Inner type.
     type Inner;
+
+    /// Which type consumes IV during [`KeyIvInit`] initialization:
+    /// [`WrapperIv`] or [`InnerIv`].
+    type IvMode;
+}
+
+/// [`InnerUser::IvMode`] of types which initialize inner type from key
+/// and consume IV themselves using [`InnerIvInit`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct WrapperIv;
+
+/// [`InnerUser::IvMode`] of types which pass key and IV to the inner type
+/// and wrap it using [`InnerInit`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct InnerIv;
+
+/// Strategy of initializing [`InnerUser`] types from key and IV.
+///
+/// This trait is sealed and implemented only for [`WrapperIv`] and [`InnerIv`].
+pub trait KeyIvInitMode<T: KeySizeUser + IvSizeUser>: sealed::Sealed {
+    /// Initialize `T` from fixed length key and nonce.
+    fn init(key: T::Key, iv: T::Iv) -> T;
+
+    /// Initialize `T` from variable length key and nonce.
+    fn init_from_slices(key: &[u8], iv: &[u8]) -> Result<T, InvalidLength>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::WrapperIv {}
+    impl Sealed for super::InnerIv {}
 }
 
 /// Resettable types.
@@ -84,14 +375,130 @@ pub trait AlgorithmName {
     fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result;
 }
 
+/// Registry identifiers and security strength of an algorithm.
+///
+/// Identifiers which are not assigned to an algorithm are equal to `None`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct AlgorithmInfo {
+    /// ASN.1 object identifier in the dotted decimal notation,
+    /// e.g. `"2.16.840.1.101.3.4.2.1"` for SHA-256.
+    pub oid: Option<&'static str>,
+    /// Identifier from the IANA "COSE Algorithms" registry.
+    pub cose_alg: Option<i64>,
+    /// Name from the IANA "JSON Web Signature and Encryption Algorithms" registry.
+    pub jose_alg: Option<&'static str>,
+    /// Security strength in bits as defined in NIST SP 800-57,
+    /// e.g. 128 for SHA-256 and AES-128.
+    pub security_bits: usize,
+}
+
+impl AlgorithmInfo {
+    /// Create metadata with the given security strength and without identifiers.
+    #[inline]
+    pub const fn new(security_bits: usize) -> Self {
+        Self {
+            oid: None,
+            cose_alg: None,
+            jose_alg: None,
+            security_bits,
+        }
+    }
+
+    /// Set ASN.1 object identifier.
+    #[inline]
+    pub const fn oid(mut self, oid: &'static str) -> Self {
+        self.oid = Some(oid);
+        self
+    }
+
+    /// Set COSE algorithm identifier.
+    #[inline]
+    pub const fn cose_alg(mut self, alg: i64) -> Self {
+        self.cose_alg = Some(alg);
+        self
+    }
+
+    /// Set JOSE algorithm name.
+    #[inline]
+    pub const fn jose_alg(mut self, alg: &'static str) -> Self {
+        self.jose_alg = Some(alg);
+        self
+    }
+}
+
+/// Trait which stores algorithm identifiers and security strength, used for
+/// building X.509, PKCS#8, CMS, COSE and JOSE structures.
+///
+/// ```
+/// use crypto_common::{AlgorithmInfo, AlgorithmMetadata};
+///
+/// struct Sha256;
+///
+/// impl AlgorithmMetadata for Sha256 {
+///     const METADATA: AlgorithmInfo = AlgorithmInfo::new(128)
+///         .oid("2.16.840.1.101.3.4.2.1")
+///         .cose_alg(-16);
+/// }
+///
+/// assert_eq!(Sha256::METADATA.oid, Some("2.16.840.1.101.3.4.2.1"));
+/// assert_eq!(Sha256::METADATA.jose_alg, None);
+/// ```
+pub trait AlgorithmMetadata {
+    /// Algorithm metadata.
+    const METADATA: AlgorithmInfo;
+}
+
 /// Types which can be initialized from key.
 pub trait KeyInit: KeySizeUser + Sized {
     /// Create new value from fixed size key.
     fn new(key: Self::Key) -> Self;
 
     /// Create new value from variable size key.
-    fn new_from_slice(key: &[u8]) -> Result<Self, TryFromSliceError> {
-        Self::Key::try_from(key).map(Self::new)
+    ///
+    /// Default implementation accepts only keys with length equal to `Key::LEN`
+    /// and reports [`KeySizeUser::KEY_SIZES`] as expected lengths on error.
+    /// Algorithms which support other lengths must override this method,
+    /// otherwise using the default implementation fails to compile:
+    ///
+    /// ```compile_fail
+    /// use crypto_common::{KeyInit, KeySizeUser, Lengths};
+    ///
+    /// struct Cipher;
+    ///
+    /// impl KeySizeUser for Cipher {
+    ///     type Key = [u8; 16];
+    ///     const KEY_SIZES: Lengths = Lengths::range(16, 32, 8);
+    /// }
+    ///
+    /// impl KeyInit for Cipher {
+    ///     fn new(_key: [u8; 16]) -> Self {
+    ///         Cipher
+    ///     }
+    /// }
+    ///
+    /// let _ = Cipher::new_from_slice(&[0u8; 24]);
+    /// ```
+    #[inline]
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        FixedKeySize::<Self>::copy_key(key).map(Self::new)
+    }
+
+    /// Create new value from secret key.
+    ///
+    /// The temporary copy of the key passed to [`new`][Self::new] is wiped
+    /// after the call. Since `new` takes the key by value, the compiler may
+    /// still leave copies of it in the callee's stack frame, so algorithms
+    /// which need stronger guarantees should override this method and
+    /// initialize their state from `key.expose_secret()` directly.
+    #[cfg(feature = "secret")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "secret")))]
+    #[inline]
+    fn new_from_secret(key: &SecretKey<Self::Key>) -> Self {
+        let mut copy = *key.expose_secret();
+        let res = Self::new(copy);
+        copy.as_mut().zeroize();
+        res
     }
 
     /// Generate random key using the provided [`CryptoRng`].
@@ -100,9 +507,17 @@ pub trait KeyInit: KeySizeUser + Sized {
     #[inline]
     fn generate_key(mut rng: impl CryptoRng + RngCore) -> Self::Key {
         let mut key = Self::Key::zero();
-        rng.fill_bytes(&mut key);
+        rng.fill_bytes(key.as_mut());
         key
     }
+
+    /// Generate random secret key using the provided [`CryptoRng`].
+    #[cfg(all(feature = "rand_core", feature = "secret"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "rand_core", feature = "secret"))))]
+    #[inline]
+    fn generate_secret_key(rng: impl CryptoRng + RngCore) -> SecretKey<Self::Key> {
+        SecretKey::generate(rng)
+    }
 }
 
 /// Types which can be initialized from key and initialization vector (nonce).
@@ -111,11 +526,34 @@ pub trait KeyIvInit: KeySizeUser + IvSizeUser + Sized {
     fn new(key: Self::Key, iv: Self::Iv) -> Self;
 
     /// Create new value from variable length key and nonce.
+    ///
+    /// Default implementation accepts only keys with length equal to `Key::LEN`
+    /// and reports [`KeySizeUser::KEY_SIZES`] as expected lengths on error.
+    /// Algorithms which support other lengths must override this method,
+    /// otherwise using the default implementation fails to compile.
     #[inline]
-    fn new_from_slices(key: &[u8], iv: &[u8]) -> Result<Self, TryFromSliceError> {
-        let key = key.try_into()?;
-        let iv = iv.try_into()?;
-        Ok(Self::new(key, iv))
+    fn new_from_slices(key: &[u8], iv: &[u8]) -> Result<Self, InvalidLength> {
+        Ok(Self::new(
+            FixedKeySize::<Self>::copy_key(key)?,
+            copy_iv(iv)?,
+        ))
+    }
+
+    /// Create new value from secret key and nonce.
+    ///
+    /// The temporary copy of the key passed to [`new`][Self::new] is wiped
+    /// after the call. Since `new` takes the key by value, the compiler may
+    /// still leave copies of it in the callee's stack frame, so algorithms
+    /// which need stronger guarantees should override this method and
+    /// initialize their state from `key.expose_secret()` directly.
+    #[cfg(feature = "secret")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "secret")))]
+    #[inline]
+    fn new_from_secret(key: &SecretKey<Self::Key>, iv: Self::Iv) -> Self {
+        let mut copy = *key.expose_secret();
+        let res = Self::new(copy, iv);
+        copy.as_mut().zeroize();
+        res
     }
 
     /// Generate random key using the provided [`CryptoRng`].
@@ -124,17 +562,25 @@ pub trait KeyIvInit: KeySizeUser + IvSizeUser + Sized {
     #[inline]
     fn generate_key(mut rng: impl CryptoRng + RngCore) -> Self::Key {
         let mut key = Self::Key::zero();
-        rng.fill_bytes(&mut key);
+        rng.fill_bytes(key.as_mut());
         key
     }
 
+    /// Generate random secret key using the provided [`CryptoRng`].
+    #[cfg(all(feature = "rand_core", feature = "secret"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "rand_core", feature = "secret"))))]
+    #[inline]
+    fn generate_secret_key(rng: impl CryptoRng + RngCore) -> SecretKey<Self::Key> {
+        SecretKey::generate(rng)
+    }
+
     /// Generate random IV using the provided [`CryptoRng`].
     #[cfg(feature = "rand_core")]
     #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
     #[inline]
     fn generate_iv(mut rng: impl CryptoRng + RngCore) -> Self::Iv {
         let mut iv = Self::Iv::zero();
-        rng.fill_bytes(&mut iv);
+        rng.fill_bytes(iv.as_mut());
         iv
     }
 
@@ -145,6 +591,19 @@ pub trait KeyIvInit: KeySizeUser + IvSizeUser + Sized {
     fn generate_key_iv(mut rng: impl CryptoRng + RngCore) -> (Self::Key, Self::Iv) {
         (Self::generate_key(&mut rng), Self::generate_iv(&mut rng))
     }
+
+    /// Generate random secret key and nonce using the provided [`CryptoRng`].
+    #[cfg(all(feature = "rand_core", feature = "secret"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "rand_core", feature = "secret"))))]
+    #[inline]
+    fn generate_secret_key_iv(
+        mut rng: impl CryptoRng + RngCore,
+    ) -> (SecretKey<Self::Key>, Self::Iv) {
+        (
+            Self::generate_secret_key(&mut rng),
+            Self::generate_iv(&mut rng),
+        )
+    }
 }
 
 /// Types which can be initialized from another type (usually block ciphers).
@@ -164,9 +623,8 @@ pub trait InnerIvInit: InnerUser + IvSizeUser + Sized {
     fn inner_iv_init(inner: Self::Inner, iv: Self::Iv) -> Self;
 
     /// Initialize value using `inner` and `iv` slice.
-    fn inner_iv_slice_init(inner: Self::Inner, iv: &[u8]) -> Result<Self, TryFromSliceError> {
-        let iv = iv.try_into()?;
-        Ok(Self::inner_iv_init(inner, iv))
+    fn inner_iv_slice_init(inner: Self::Inner, iv: &[u8]) -> Result<Self, InvalidLength> {
+        copy_iv(iv).map(|iv| Self::inner_iv_init(inner, iv))
     }
 
     /// Generate random IV using the provided [`CryptoRng`].
@@ -175,7 +633,7 @@ pub trait InnerIvInit: InnerUser + IvSizeUser + Sized {
     #[inline]
     fn generate_iv(mut rng: impl CryptoRng + RngCore) -> Self::Iv {
         let mut iv = Self::Iv::zero();
-        rng.fill_bytes(&mut iv);
+        rng.fill_bytes(iv.as_mut());
         iv
     }
 }
@@ -186,59 +644,77 @@ where
     T::Inner: KeySizeUser,
 {
     type Key = <T::Inner as KeySizeUser>::Key;
+    const KEY_SIZES: Lengths = <T::Inner as KeySizeUser>::KEY_SIZES;
 }
 
-impl<T> KeyIvInit for T
+impl<T> IvSizeUser for T
+where
+    T: InnerInit,
+    T::Inner: IvSizeUser,
+{
+    type Iv = <T::Inner as IvSizeUser>::Iv;
+}
+
+impl<T> KeyIvInitMode<T> for WrapperIv
 where
     T: InnerIvInit,
     T::Inner: KeyInit,
 {
     #[inline]
-    fn new(key: Self::Key, iv: Self::Iv) -> Self {
-        Self::inner_iv_init(T::Inner::new(key), iv)
+    fn init(key: Key<T>, iv: Iv<T>) -> T {
+        T::inner_iv_init(T::Inner::new(key), iv)
     }
 
     #[inline]
-    fn new_from_slices(key: &[u8], iv: &[u8]) -> Result<Self, TryFromSliceError> {
+    fn init_from_slices(key: &[u8], iv: &[u8]) -> Result<T, InvalidLength> {
         T::Inner::new_from_slice(key).and_then(|i| T::inner_iv_slice_init(i, iv))
     }
 }
 
-impl<T> KeyInit for T
+impl<T> KeyIvInitMode<T> for InnerIv
 where
     T: InnerInit,
-    T::Inner: KeyInit,
+    T::Inner: KeyIvInit,
 {
     #[inline]
-    fn new(key: Self::Key) -> Self {
-        Self::inner_init(T::Inner::new(key))
+    fn init(key: Key<T>, iv: Iv<T>) -> T {
+        T::inner_init(T::Inner::new(key, iv))
     }
 
     #[inline]
-    fn new_from_slice(key: &[u8]) -> Result<Self, TryFromSliceError> {
-        T::Inner::new_from_slice(key).map(Self::inner_init)
+    fn init_from_slices(key: &[u8], iv: &[u8]) -> Result<T, InvalidLength> {
+        T::Inner::new_from_slices(key, iv).map(T::inner_init)
     }
 }
 
-// Unfortunately this blanket impl is impossible without mutually
-// exclusive traits, see: https://github.com/rust-lang/rfcs/issues/1053
-// or at the very least without: https://github.com/rust-lang/rust/issues/20400
-/*
 impl<T> KeyIvInit for T
 where
-    T: InnerInit,
-    T::Inner: KeyIvInit,
+    T: InnerUser + KeySizeUser + IvSizeUser,
+    T::IvMode: KeyIvInitMode<T>,
 {
     #[inline]
-    fn new(key: &Self::Key, iv: &Self::Iv) -> Self {
-        Self::inner_init(T::Inner::new(key, iv))
+    fn new(key: Self::Key, iv: Self::Iv) -> Self {
+        T::IvMode::init(key, iv)
     }
 
     #[inline]
     fn new_from_slices(key: &[u8], iv: &[u8]) -> Result<Self, InvalidLength> {
-        T::Inner::new_from_slice(key)
-            .map_err(|_| InvalidLength)
-            .map(Self::inner_init)
+        T::IvMode::init_from_slices(key, iv)
+    }
+}
+
+impl<T> KeyInit for T
+where
+    T: InnerInit,
+    T::Inner: KeyInit,
+{
+    #[inline]
+    fn new(key: Self::Key) -> Self {
+        Self::inner_init(T::Inner::new(key))
+    }
+
+    #[inline]
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        T::Inner::new_from_slice(key).map(Self::inner_init)
     }
 }
-*/
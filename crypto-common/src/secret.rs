@@ -0,0 +1,123 @@
+use crate::{array::Array, copy_key, InvalidLength, Lengths};
+use core::fmt;
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+#[cfg(feature = "serde")]
+use array::serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "rand_core")]
+use rand_core::{CryptoRng, RngCore};
+
+/// Container for secret key material.
+///
+/// Contents are zeroized on drop and compared in constant time.
+/// Unlike bare key arrays, this type is not `Copy` and its `Debug`
+/// implementation does not reveal the key.
+pub struct SecretKey<K: Array>(K);
+
+impl<K: Array> SecretKey<K> {
+    /// Wrap `key` into the container.
+    #[inline]
+    pub fn new(key: K) -> Self {
+        Self(key)
+    }
+
+    /// Create zero-initialized key.
+    #[inline]
+    pub fn zero() -> Self {
+        Self(K::zero())
+    }
+
+    /// Copy key from the `slice`.
+    ///
+    /// Returns an error if length of `slice` is not equal to `K::LEN`.
+    #[inline]
+    pub fn from_slice(slice: &[u8]) -> Result<Self, InvalidLength> {
+        copy_key(slice, Lengths::fixed(K::LEN)).map(Self)
+    }
+
+    /// Generate random key using the provided [`CryptoRng`].
+    ///
+    /// Key is generated directly inside the container.
+    #[cfg(feature = "rand_core")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+    #[inline]
+    pub fn generate(mut rng: impl CryptoRng + RngCore) -> Self {
+        let mut key = Self::zero();
+        rng.fill_bytes(key.0.as_mut());
+        key
+    }
+
+    /// Get reference to the secret key.
+    #[inline]
+    pub fn expose_secret(&self) -> &K {
+        &self.0
+    }
+
+    /// Get mutable reference to the secret key.
+    #[inline]
+    pub fn expose_secret_mut(&mut self) -> &mut K {
+        &mut self.0
+    }
+}
+
+impl<K: Array> Clone for SecretKey<K> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+impl<K: Array> From<K> for SecretKey<K> {
+    #[inline]
+    fn from(key: K) -> Self {
+        Self::new(key)
+    }
+}
+
+impl<K: Array> ConstantTimeEq for SecretKey<K> {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.as_ref().ct_eq(other.0.as_ref())
+    }
+}
+
+impl<K: Array> PartialEq for SecretKey<K> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<K: Array> Eq for SecretKey<K> {}
+
+impl<K: Array> fmt::Debug for SecretKey<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey([REDACTED])")
+    }
+}
+
+impl<K: Array> Drop for SecretKey<K> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.as_mut().zeroize();
+    }
+}
+
+impl<K: Array> ZeroizeOnDrop for SecretKey<K> {}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<K: Array> Serialize for SecretKey<K> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        array::serde::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, K: Array> Deserialize<'de> for SecretKey<K> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        array::serde::deserialize(deserializer).map(Self::new)
+    }
+}
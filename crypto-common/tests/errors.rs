@@ -0,0 +1,99 @@
+use crypto_common::{
+    InvalidLength, IvSizeUser, KeyInit, KeyIvInit, KeySizeUser, LengthParam, Lengths,
+};
+
+#[test]
+fn display() {
+    let err = InvalidLength::key(Lengths::fixed(16), 15);
+    assert_eq!(err.to_string(), "invalid key length: expected 16, got 15");
+
+    let err = InvalidLength::key(Lengths::range(16, 32, 8), 20);
+    assert_eq!(
+        err.to_string(),
+        "invalid key length: expected 16..=32 (step 8), got 20"
+    );
+
+    let err = InvalidLength::key(Lengths::range(1, 256, 1), 0);
+    assert_eq!(
+        err.to_string(),
+        "invalid key length: expected 1..=256, got 0"
+    );
+
+    let err = InvalidLength::key(Lengths::Set(&[16, 24, 32]), 8);
+    assert_eq!(
+        err.to_string(),
+        "invalid key length: expected one of [16, 24, 32], got 8"
+    );
+
+    let err = InvalidLength::iv(12, 16);
+    assert_eq!(err.to_string(), "invalid IV length: expected 12, got 16");
+}
+
+#[test]
+fn constructors() {
+    let err = InvalidLength::key(Lengths::ANY, 3);
+    assert_eq!(err.param, LengthParam::Key);
+    assert_eq!(err.expected, Lengths::ANY);
+    assert_eq!(err.actual, 3);
+
+    let err = InvalidLength::iv(12, 16);
+    assert_eq!(err.param, LengthParam::Iv);
+    assert_eq!(err.expected, Lengths::fixed(12));
+    assert_eq!(err.actual, 16);
+}
+
+struct Cipher;
+
+impl KeySizeUser for Cipher {
+    type Key = [u8; 16];
+}
+
+impl IvSizeUser for Cipher {
+    type Iv = [u8; 12];
+}
+
+impl KeyIvInit for Cipher {
+    fn new(_key: [u8; 16], _iv: [u8; 12]) -> Self {
+        Self
+    }
+}
+
+struct Mac;
+
+impl KeySizeUser for Mac {
+    type Key = [u8; 32];
+}
+
+impl KeyInit for Mac {
+    fn new(_key: [u8; 32]) -> Self {
+        Self
+    }
+}
+
+#[test]
+fn new_from_slice_param() {
+    assert!(Mac::new_from_slice(&[0; 32]).is_ok());
+    assert_eq!(
+        Mac::new_from_slice(&[0; 31]).err(),
+        Some(InvalidLength::key(Lengths::fixed(32), 31))
+    );
+}
+
+#[test]
+fn new_from_slices_param() {
+    assert!(Cipher::new_from_slices(&[0; 16], &[0; 12]).is_ok());
+
+    // invalid key
+    let err = Cipher::new_from_slices(&[0; 15], &[0; 12]).err().unwrap();
+    assert_eq!(err, InvalidLength::key(Lengths::fixed(16), 15));
+    assert_eq!(err.to_string(), "invalid key length: expected 16, got 15");
+
+    // invalid IV
+    let err = Cipher::new_from_slices(&[0; 16], &[0; 16]).err().unwrap();
+    assert_eq!(err, InvalidLength::iv(12, 16));
+    assert_eq!(err.to_string(), "invalid IV length: expected 12, got 16");
+
+    // key is checked first
+    let err = Cipher::new_from_slices(&[0; 32], &[]).err().unwrap();
+    assert_eq!(err, InvalidLength::key(Lengths::fixed(16), 32));
+}
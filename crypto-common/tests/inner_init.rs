@@ -0,0 +1,157 @@
+//! Both `InnerUser::IvMode` compositions get `KeyInit`/`KeyIvInit`
+//! through the blanket impls.
+use crypto_common::{
+    InnerInit, InnerIv, InnerIvInit, InnerUser, InvalidLength, Iv, IvSizeUser, Key, KeyInit,
+    KeyIvInit, KeySizeUser, Lengths, WrapperIv,
+};
+
+fn assert_key_init<T: KeyInit>() {}
+fn assert_key_iv_init<T: KeyIvInit>() {}
+
+/// Block cipher like type taking only a key.
+struct Cipher([u8; 16]);
+
+impl KeySizeUser for Cipher {
+    type Key = [u8; 16];
+}
+
+impl KeyInit for Cipher {
+    fn new(key: [u8; 16]) -> Self {
+        Self(key)
+    }
+}
+
+/// Inner takes key, wrapper takes IV, e.g. a block mode.
+struct Mode {
+    cipher: Cipher,
+    iv: [u8; 8],
+}
+
+impl InnerUser for Mode {
+    type Inner = Cipher;
+    type IvMode = WrapperIv;
+}
+
+impl IvSizeUser for Mode {
+    type Iv = [u8; 8];
+}
+
+impl InnerIvInit for Mode {
+    fn inner_iv_init(cipher: Cipher, iv: [u8; 8]) -> Self {
+        Self { cipher, iv }
+    }
+}
+
+/// Stream cipher core like type taking key and IV.
+struct Core {
+    key: [u8; 32],
+    iv: [u8; 12],
+}
+
+impl KeySizeUser for Core {
+    type Key = [u8; 32];
+}
+
+impl IvSizeUser for Core {
+    type Iv = [u8; 12];
+}
+
+impl KeyIvInit for Core {
+    fn new(key: [u8; 32], iv: [u8; 12]) -> Self {
+        Self { key, iv }
+    }
+}
+
+/// Inner takes key and IV, e.g. a buffering wrapper around a core.
+struct Wrapper(Core);
+
+impl InnerUser for Wrapper {
+    type Inner = Core;
+    type IvMode = InnerIv;
+}
+
+impl InnerInit for Wrapper {
+    fn inner_init(core: Core) -> Self {
+        Self(core)
+    }
+}
+
+/// `InnerIv` wrapper around a `WrapperIv` type.
+struct ModeWrapper(Mode);
+
+impl InnerUser for ModeWrapper {
+    type Inner = Mode;
+    type IvMode = InnerIv;
+}
+
+impl InnerInit for ModeWrapper {
+    fn inner_init(mode: Mode) -> Self {
+        Self(mode)
+    }
+}
+
+#[test]
+fn wrapper_iv() {
+    assert_key_iv_init::<Mode>();
+
+    let mode = Mode::new([1; 16], [2; 8]);
+    assert_eq!((mode.cipher.0, mode.iv), ([1; 16], [2; 8]));
+
+    let mode = Mode::new_from_slices(&[3; 16], &[4; 8]).unwrap();
+    assert_eq!((mode.cipher.0, mode.iv), ([3; 16], [4; 8]));
+
+    let err = Mode::new_from_slices(&[3; 15], &[4; 8]).err().unwrap();
+    assert_eq!(err, InvalidLength::key(Lengths::fixed(16), 15));
+    let err = Mode::new_from_slices(&[3; 16], &[4; 9]).err().unwrap();
+    assert_eq!(err, InvalidLength::iv(8, 9));
+}
+
+#[test]
+fn inner_iv() {
+    assert_key_iv_init::<Wrapper>();
+
+    let key: Key<Wrapper> = [1; 32];
+    let iv: Iv<Wrapper> = [2; 12];
+    let w = Wrapper::new(key, iv);
+    assert_eq!((w.0.key, w.0.iv), ([1; 32], [2; 12]));
+
+    let w = Wrapper::new_from_slices(&[3; 32], &[4; 12]).unwrap();
+    assert_eq!((w.0.key, w.0.iv), ([3; 32], [4; 12]));
+
+    let err = Wrapper::new_from_slices(&[3; 16], &[4; 12]).err().unwrap();
+    assert_eq!(err, InvalidLength::key(Lengths::fixed(32), 16));
+    let err = Wrapper::new_from_slices(&[3; 32], &[4; 8]).err().unwrap();
+    assert_eq!(err, InvalidLength::iv(12, 8));
+}
+
+#[test]
+fn nested() {
+    assert_key_iv_init::<ModeWrapper>();
+
+    let w = ModeWrapper::new_from_slices(&[1; 16], &[2; 8]).unwrap();
+    assert_eq!((w.0.cipher.0, w.0.iv), ([1; 16], [2; 8]));
+    assert!(ModeWrapper::new_from_slices(&[1; 16], &[2; 7]).is_err());
+}
+
+/// `InnerInit` types over key-only inner types get `KeyInit`.
+struct KeyWrapper(Cipher);
+
+impl InnerUser for KeyWrapper {
+    type Inner = Cipher;
+    type IvMode = InnerIv;
+}
+
+impl InnerInit for KeyWrapper {
+    fn inner_init(cipher: Cipher) -> Self {
+        Self(cipher)
+    }
+}
+
+#[test]
+fn key_only() {
+    assert_key_init::<KeyWrapper>();
+
+    let w = KeyWrapper::new_from_slice(&[5; 16]).unwrap();
+    assert_eq!(w.0 .0, [5; 16]);
+    assert!(KeyWrapper::new_from_slice(&[5; 17]).is_err());
+}
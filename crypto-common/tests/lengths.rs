@@ -0,0 +1,161 @@
+use crypto_common::{InvalidLength, KeyInit, KeySizeUser, LengthParam, Lengths};
+
+#[test]
+fn fixed() {
+    let sizes = Lengths::fixed(16);
+    assert_eq!(sizes, Lengths::range(16, 16, 1));
+    assert!(sizes.contains(16));
+    assert!(!sizes.contains(0));
+    assert!(!sizes.contains(15));
+    assert!(!sizes.contains(17));
+    assert!(!sizes.contains(32));
+}
+
+#[test]
+fn range() {
+    let sizes = Lengths::range(16, 32, 8);
+    for len in 0..64 {
+        assert_eq!(sizes.contains(len), [16, 24, 32].contains(&len), "{}", len);
+    }
+
+    let sizes = Lengths::range(1, 256, 1);
+    assert!(!sizes.contains(0));
+    assert!((1..=256).all(|len| sizes.contains(len)));
+    assert!(!sizes.contains(257));
+
+    // step which does not divide `max - min`
+    let sizes = Lengths::range(10, 20, 3);
+    for len in 0..32 {
+        assert_eq!(
+            sizes.contains(len),
+            [10, 13, 16, 19].contains(&len),
+            "{}",
+            len
+        );
+    }
+
+    // zero step allows only `min`
+    let sizes = Lengths::range(8, 16, 0);
+    assert!(sizes.contains(8));
+    assert!(!sizes.contains(9));
+    assert!(!sizes.contains(16));
+
+    // empty range
+    let sizes = Lengths::range(16, 8, 1);
+    assert!((0..32).all(|len| !sizes.contains(len)));
+}
+
+#[test]
+fn set() {
+    let sizes = Lengths::Set(&[16, 24, 32]);
+    for len in 0..64 {
+        assert_eq!(sizes.contains(len), [16, 24, 32].contains(&len), "{}", len);
+    }
+
+    let sizes = Lengths::Set(&[]);
+    assert!((0..64).all(|len| !sizes.contains(len)));
+    assert!(!sizes.contains(usize::MAX));
+}
+
+#[test]
+fn any() {
+    let sizes = Lengths::ANY;
+    assert!(sizes.contains(0));
+    assert!(sizes.contains(1));
+    assert!(sizes.contains(1 << 20));
+    assert!(sizes.contains(usize::MAX - 1));
+    assert!(sizes.contains(usize::MAX));
+}
+
+#[test]
+fn check() {
+    let sizes = Lengths::range(16, 32, 8);
+    assert_eq!(sizes.check_key(24), Ok(()));
+    assert_eq!(sizes.check_key(20), Err(InvalidLength::key(sizes, 20)));
+    assert_eq!(Lengths::ANY.check_key(0), Ok(()));
+    assert_eq!(
+        Lengths::Set(&[]).check_key(0),
+        Err(InvalidLength::key(Lengths::Set(&[]), 0))
+    );
+}
+
+#[test]
+fn display() {
+    assert_eq!(Lengths::fixed(16).to_string(), "16");
+    assert_eq!(Lengths::range(1, 256, 1).to_string(), "1..=256");
+    assert_eq!(Lengths::range(16, 32, 8).to_string(), "16..=32 (step 8)");
+    assert_eq!(Lengths::Set(&[16, 32]).to_string(), "one of [16, 32]");
+}
+
+/// Key of fixed size relying on the default slice constructor.
+struct Fixed([u8; 16]);
+
+impl KeySizeUser for Fixed {
+    type Key = [u8; 16];
+}
+
+impl KeyInit for Fixed {
+    fn new(key: [u8; 16]) -> Self {
+        Self(key)
+    }
+}
+
+/// Key of 1 to 32 bytes, zero-padded up to `Key::LEN`.
+struct Ranged {
+    key: [u8; 32],
+    len: usize,
+}
+
+impl KeySizeUser for Ranged {
+    type Key = [u8; 32];
+    const KEY_SIZES: Lengths = Lengths::range(1, 32, 1);
+}
+
+impl KeyInit for Ranged {
+    fn new(key: [u8; 32]) -> Self {
+        Self { key, len: 32 }
+    }
+
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        Self::KEY_SIZES.check_key(key.len())?;
+        let mut buf = [0u8; 32];
+        buf[..key.len()].copy_from_slice(key);
+        Ok(Self {
+            key: buf,
+            len: key.len(),
+        })
+    }
+}
+
+#[test]
+fn new_from_slice_fixed() {
+    assert_eq!(Fixed::KEY_SIZES, Lengths::fixed(16));
+    let key: Vec<u8> = (0..16).collect();
+    assert_eq!(Fixed::new_from_slice(&key).unwrap().0[..], key[..]);
+
+    for &len in &[0, 1, 15, 17, 32] {
+        let err = Fixed::new_from_slice(&vec![0; len]).err().unwrap();
+        assert_eq!(err.param, LengthParam::Key);
+        assert_eq!(err.expected, Lengths::fixed(16));
+        assert_eq!(err.actual, len);
+    }
+}
+
+#[test]
+fn new_from_slice_ranged() {
+    for len in 1..=32 {
+        let key: Vec<u8> = (1..=len as u8).collect();
+        let r = Ranged::new_from_slice(&key).unwrap();
+        assert_eq!(r.len, len);
+        assert_eq!(r.key[..len], key[..]);
+        assert!(r.key[len..].iter().all(|&b| b == 0));
+    }
+
+    let r = Ranged::new([0xff; 32]);
+    assert_eq!(r.len, 32);
+
+    for &len in &[0, 33, 64] {
+        let err = Ranged::new_from_slice(&vec![0; len]).err().unwrap();
+        assert_eq!(err, InvalidLength::key(Lengths::range(1, 32, 1), len));
+    }
+}
@@ -0,0 +1,330 @@
+//! Combinators which build new hash functions out of existing ones.
+//!
+//! - [`Concat`] hashes data with two hash functions and concatenates
+//!   their outputs;
+//! - [`Truncated`] keeps only the first `N` bytes of the output;
+//! - [`Prefixed`] hashes data prefixed with a fixed domain separation tag
+//!   without rehashing the prefix for every message.
+//!
+//! Combinators preserve [`HashMarker`], [`FixedOutput`], [`Reset`] and
+//! [`FixedOutputReset`] implementations of the wrapped hashers, so they
+//! can be used with [`Digest`][crate::Digest] and [`DynDigest`][crate::DynDigest].
+//!
+//! ```
+//! use digest::combinators::{Concat, Prefixed, Truncated};
+//! use digest::{Digest, FixedOutput};
+//! # use digest::{HashMarker, OutputSizeUser};
+//! # #[derive(Clone, Default)]
+//! # struct Sha256(u32);
+//! # impl HashMarker for Sha256 {}
+//! # impl OutputSizeUser for Sha256 { type Output = [u8; 4]; }
+//! # impl digest::Update for Sha256 {
+//! #     fn update(&mut self, data: &[u8]) {
+//! #         for &b in data { self.0 = self.0.rotate_left(5) ^ u32::from(b) }
+//! #     }
+//! # }
+//! # impl FixedOutput for Sha256 {
+//! #     fn finalize_into(self, out: &mut [u8; 4]) { *out = self.0.to_le_bytes() }
+//! # }
+//! # type Sha512 = Sha256;
+//!
+//! // output of both hashes, e.g. during migration to a new algorithm
+//! let both: [u8; 8] = Concat::<Sha256, Sha512, 8>::digest(b"data");
+//! assert_eq!(both[..4], Sha256::digest(b"data"));
+//!
+//! // the first two bytes of the output
+//! let short: [u8; 2] = Truncated::<Sha256, 2>::digest(b"data");
+//! assert_eq!(short, both[..2]);
+//!
+//! // prefix is hashed once and the saved state is reused
+//! let mut tagged = Prefixed::<Sha256>::new(b"my protocol v1");
+//! digest::Update::update(&mut tagged, b"data");
+//! assert_eq!(tagged.finalize_fixed(), Sha256::digest(b"my protocol v1data"));
+//! ```
+
+use crate::{FixedOutput, FixedOutputReset, HashMarker, InvalidState, SerializableState, Update};
+use array::Array;
+use crypto_common::{Output, OutputSizeUser, Reset};
+
+/// Hasher which computes hashes of the same data using `A` and `B` and
+/// returns concatenation of their outputs.
+///
+/// `N` must be equal to sum of output sizes of `A` and `B`, otherwise
+/// compilation fails.
+#[derive(Clone, Debug)]
+pub struct Concat<A, B, const N: usize> {
+    a: A,
+    b: B,
+}
+
+impl<A, B, const N: usize> Concat<A, B, N>
+where
+    A: OutputSizeUser,
+    B: OutputSizeUser,
+{
+    const OUTPUT_SIZE_CHECK: () = assert!(
+        N == <Output<A> as Array>::LEN + <Output<B> as Array>::LEN,
+        "output size must be equal to sum of output sizes"
+    );
+
+    /// Combine two hasher instances.
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    pub fn new(a: A, b: B) -> Self {
+        let () = Self::OUTPUT_SIZE_CHECK;
+        Self { a, b }
+    }
+
+    /// Decompose combinator into the inner hashers.
+    #[inline]
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A, B, const N: usize> Default for Concat<A, B, N>
+where
+    A: OutputSizeUser + Default,
+    B: OutputSizeUser + Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(A::default(), B::default())
+    }
+}
+
+impl<A: HashMarker, B: HashMarker, const N: usize> HashMarker for Concat<A, B, N> {}
+
+impl<A, B, const N: usize> OutputSizeUser for Concat<A, B, N> {
+    type Output = [u8; N];
+}
+
+impl<A: Update, B: Update, const N: usize> Update for Concat<A, B, N> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.a.update(data);
+        self.b.update(data);
+    }
+}
+
+impl<A: FixedOutput, B: FixedOutput, const N: usize> FixedOutput for Concat<A, B, N> {
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    fn finalize_into(self, out: &mut [u8; N]) {
+        let () = Self::OUTPUT_SIZE_CHECK;
+        let (a, b) = out.split_at_mut(<Output<A> as Array>::LEN);
+        a.copy_from_slice(self.a.finalize_fixed().as_ref());
+        b.copy_from_slice(self.b.finalize_fixed().as_ref());
+    }
+}
+
+impl<A: Reset, B: Reset, const N: usize> Reset for Concat<A, B, N> {
+    #[inline]
+    fn reset(&mut self) {
+        self.a.reset();
+        self.b.reset();
+    }
+}
+
+impl<A, B, const N: usize> FixedOutputReset for Concat<A, B, N>
+where
+    A: FixedOutputReset,
+    B: FixedOutputReset,
+{
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    fn finalize_into_reset(&mut self, out: &mut [u8; N]) {
+        let () = Self::OUTPUT_SIZE_CHECK;
+        let (a, b) = out.split_at_mut(<Output<A> as Array>::LEN);
+        a.copy_from_slice(self.a.finalize_fixed_reset().as_ref());
+        b.copy_from_slice(self.b.finalize_fixed_reset().as_ref());
+    }
+}
+
+/// Hasher which truncates output of `D` to the first `N` bytes.
+///
+/// Note that truncated outputs of some hash functions are defined as
+/// separate algorithms with distinct initial state (e.g. SHA-512/256),
+/// which produce results different from plain truncation. Such algorithms
+/// can be built by truncating [`Prefixed`] hasher, which resets to the
+/// given initial state, see [`Truncated::from_serialized_state`].
+///
+/// `N` must not be bigger than output size of `D`, otherwise
+/// compilation fails.
+#[derive(Clone, Debug)]
+pub struct Truncated<D, const N: usize> {
+    hasher: D,
+}
+
+impl<D: OutputSizeUser, const N: usize> Truncated<D, N> {
+    const OUTPUT_SIZE_CHECK: () = assert!(
+        N <= <Output<D> as Array>::LEN,
+        "truncated output size must not be bigger than output size"
+    );
+
+    /// Wrap hasher instance.
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    pub fn new(hasher: D) -> Self {
+        let () = Self::OUTPUT_SIZE_CHECK;
+        Self { hasher }
+    }
+
+    /// Get the inner hasher.
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.hasher
+    }
+}
+
+impl<D: OutputSizeUser + Update + Clone, const N: usize> Truncated<Prefixed<D>, N> {
+    /// Create hasher which starts from a serialized state of `D` and
+    /// restores it on reset, e.g. SHA-512 with the SHA-512/256 initial values.
+    ///
+    /// Use [`Prefixed::from_hasher`] with [`Truncated::new`] to start
+    /// from a hasher instance instead.
+    #[inline]
+    pub fn from_serialized_state(state: &[u8]) -> Result<Self, InvalidState>
+    where
+        D: SerializableState,
+    {
+        Prefixed::from_serialized_state(state).map(Self::new)
+    }
+}
+
+impl<D: OutputSizeUser + Default, const N: usize> Default for Truncated<D, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(D::default())
+    }
+}
+
+impl<D: HashMarker, const N: usize> HashMarker for Truncated<D, N> {}
+
+impl<D, const N: usize> OutputSizeUser for Truncated<D, N> {
+    type Output = [u8; N];
+}
+
+impl<D: Update, const N: usize> Update for Truncated<D, N> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+}
+
+impl<D: FixedOutput, const N: usize> FixedOutput for Truncated<D, N> {
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    fn finalize_into(self, out: &mut [u8; N]) {
+        let () = Self::OUTPUT_SIZE_CHECK;
+        out.copy_from_slice(&self.hasher.finalize_fixed().as_ref()[..N]);
+    }
+}
+
+impl<D: Reset, const N: usize> Reset for Truncated<D, N> {
+    #[inline]
+    fn reset(&mut self) {
+        self.hasher.reset();
+    }
+}
+
+impl<D: FixedOutputReset, const N: usize> FixedOutputReset for Truncated<D, N> {
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    fn finalize_into_reset(&mut self, out: &mut [u8; N]) {
+        let () = Self::OUTPUT_SIZE_CHECK;
+        out.copy_from_slice(&self.hasher.finalize_fixed_reset().as_ref()[..N]);
+    }
+}
+
+/// Hasher which prepends a fixed prefix to the hashed data.
+///
+/// State of `D` after processing the prefix is saved, so resetting the hasher
+/// restores it without rehashing the prefix. With core hashers wrapped into
+/// [`CoreWrapper`][crate::core_api::CoreWrapper] the saved state also
+/// includes the buffered part of the prefix.
+///
+/// Since the prefix is provided at runtime, the hasher does not implement
+/// [`Default`] and thus [`Digest`][crate::Digest], but it can be used
+/// through [`FixedOutput`] and [`DynDigest`][crate::DynDigest].
+#[derive(Clone, Debug)]
+pub struct Prefixed<D> {
+    initial: D,
+    hasher: D,
+}
+
+impl<D: Update + Clone> Prefixed<D> {
+    /// Create hasher which prepends `prefix` to the hashed data.
+    #[inline]
+    pub fn new(prefix: &[u8]) -> Self
+    where
+        D: Default,
+    {
+        let mut hasher = D::default();
+        hasher.update(prefix);
+        Self::from_hasher(hasher)
+    }
+
+    /// Create hasher using state of `hasher` which has already
+    /// processed the prefix.
+    #[inline]
+    pub fn from_hasher(hasher: D) -> Self {
+        Self {
+            initial: hasher.clone(),
+            hasher,
+        }
+    }
+
+    /// Create hasher from a serialized state of `D` which has already
+    /// processed the prefix.
+    ///
+    /// It allows to store precomputed prefix states as constants.
+    #[inline]
+    pub fn from_serialized_state(state: &[u8]) -> Result<Self, InvalidState>
+    where
+        D: SerializableState,
+    {
+        D::deserialize_state(state).map(Self::from_hasher)
+    }
+
+    /// Get reference to the saved state of `D` after processing the prefix.
+    #[inline]
+    pub fn prefix_state(&self) -> &D {
+        &self.initial
+    }
+}
+
+impl<D: HashMarker> HashMarker for Prefixed<D> {}
+
+impl<D: OutputSizeUser> OutputSizeUser for Prefixed<D> {
+    type Output = D::Output;
+}
+
+impl<D: Update> Update for Prefixed<D> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+}
+
+impl<D: FixedOutput> FixedOutput for Prefixed<D> {
+    #[inline]
+    fn finalize_into(self, out: &mut Self::Output) {
+        self.hasher.finalize_into(out);
+    }
+}
+
+impl<D: Clone> Reset for Prefixed<D> {
+    #[inline]
+    fn reset(&mut self) {
+        self.hasher = self.initial.clone();
+    }
+}
+
+impl<D: FixedOutput + Clone> FixedOutputReset for Prefixed<D> {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut Self::Output) {
+        let hasher = core::mem::replace(&mut self.hasher, self.initial.clone());
+        hasher.finalize_into(out);
+    }
+}
@@ -3,9 +3,20 @@
 //! Usage of traits in this module in user code is discouraged. Instead use
 //! core algorithm wrapped by the wrapper types, which implement the
 //! higher-level traits.
-use crate::InvalidOutputSize;
+//!
+//! With enabled `zeroize` feature the wrappers wipe data buffered in their
+//! block buffers on reset and drop. If the core implements `Zeroize`, the
+//! wrapper implements it as well and wipes both the core and the buffer.
+//! Wiping core state on drop, e.g. keyed state of a MAC core, is done by the
+//! core in its own `Drop` impl. Such cores should implement `ZeroizeOnDrop`,
+//! in which case wrappers around them implement the trait as well.
+use crate::{InvalidBufferSize, InvalidOutputSize, InvalidState};
+use array::Array;
+use core::convert::TryFrom;
 
-pub use crypto_common::{AlgorithmName, BlockSizeUser, OutputSizeUser, Reset};
+pub use crypto_common::{
+    AlgorithmInfo, AlgorithmMetadata, AlgorithmName, BlockSizeUser, OutputSizeUser, Reset,
+};
 
 use block_buffer::{BlockBuffer, BufferKind};
 
@@ -28,9 +39,32 @@ pub trait UpdateCore: BlockSizeUser {
     fn update_blocks(&mut self, blocks: &[Self::Block]);
 }
 
+/// Core trait for hash functions able to update several independent states
+/// in lockstep, e.g. using SIMD lanes.
+///
+/// Implementing this trait enables [`BatchDigest`] for [`CoreWrapper`],
+/// which handles buffering, padding and finalization of messages
+/// with uneven lengths.
+///
+/// [`BatchDigest`]: crate::BatchDigest
+pub trait ParUpdateCore: UpdateCore + Sized {
+    /// Number of states which are most efficiently processed in parallel.
+    const PAR_STATES: usize;
+
+    /// Update every state in `states` using the block with the same index
+    /// in `blocks`.
+    ///
+    /// Lengths of `states` and `blocks` are always equal and not bigger than
+    /// [`PAR_STATES`][Self::PAR_STATES], but may be smaller than it.
+    fn update_par_blocks(states: &mut [Self], blocks: &[Self::Block]);
+}
+
 /// Types which use [`BlockBuffer`] functionality.
 pub trait BufferKindUser: BlockSizeUser {
     /// Block buffer kind over which type operates.
+    ///
+    /// The kind also selects the buffer cursor type, e.g. `Eager<u16>`
+    /// is needed for blocks bigger than 256 bytes.
     type BufferKind: BufferKind;
 }
 
@@ -57,6 +91,23 @@ pub trait XofReaderCore: BlockSizeUser {
     fn read_block(&mut self) -> Self::Block;
 }
 
+/// Core reader trait for XOFs which support random access to the output,
+/// e.g. counter-based or tree-based constructions.
+///
+/// Implementing this trait enables [`XofReaderSeek`] for
+/// [`XofReaderCoreWrapper`].
+///
+/// [`XofReaderSeek`]: crate::XofReaderSeek
+pub trait XofReaderSeekCore: XofReaderCore {
+    /// Get index of the block which will be returned by the next
+    /// [`read_block`][XofReaderCore::read_block] call.
+    fn get_block_pos(&self) -> u64;
+
+    /// Set index of the block which will be returned by the next
+    /// [`read_block`][XofReaderCore::read_block] call.
+    fn set_block_pos(&mut self, pos: u64);
+}
+
 /// Core trait for hash functions with variable output size.
 ///
 /// Maximum output size is equal to [`OutputSizeUser::OutputSize`].
@@ -91,6 +142,155 @@ pub trait VariableOutputCore: UpdateCore + OutputSizeUser + BufferKindUser + Siz
     fn finalize_variable_core(&mut self, buffer: &mut Buffer<Self>, out: &mut Self::Output);
 }
 
+/// Metadata of [`VariableOutputCore`] algorithms, which usually
+/// depends on the selected output size.
+///
+/// Used by [`CtVariableCoreWrapper`] to implement [`AlgorithmMetadata`]
+/// and by [`RtVariableCoreWrapper::metadata`].
+pub trait VariableOutputMetadata: VariableOutputCore {
+    /// Supported output sizes in bytes and the corresponding metadata.
+    const OUTPUT_METADATA: &'static [(usize, AlgorithmInfo)];
+}
+
+/// Find metadata of output size `output_size` in `table`.
+pub(crate) const fn find_metadata(
+    table: &'static [(usize, AlgorithmInfo)],
+    output_size: usize,
+) -> Option<AlgorithmInfo> {
+    let mut i = 0;
+    while i < table.len() {
+        if table[i].0 == output_size {
+            return Some(table[i].1);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Core types with a portable serialized representation of their state.
+///
+/// Implementing this trait enables [`SerializableState`] for the wrappers
+/// around the core. The serialized state must not depend on the target
+/// platform, e.g. integers should be stored in a fixed byte order.
+///
+/// [`SerializableState`]: crate::SerializableState
+pub trait SerializableStateCore: Sized {
+    /// Serialized core state.
+    type SerializedState: Array;
+
+    /// Serialize core state.
+    fn serialize_state_core(&self) -> Self::SerializedState;
+
+    /// Restore core from the serialized state.
+    ///
+    /// Returns [`InvalidState`] if `state` is not a valid state of the core.
+    fn deserialize_state_core(state: &Self::SerializedState) -> Result<Self, InvalidState>;
+}
+
+/// Version of the state format used by the wrappers.
+const STATE_VERSION: u8 = 1;
+
+/// Size of the serialized block buffer with blocks of `block_size` bytes.
+///
+/// Buffer is stored as a little-endian `u64` cursor position followed
+/// by the block.
+pub(crate) const fn buffer_state_size(block_size: usize) -> usize {
+    8 + block_size
+}
+
+/// Writer of the versioned state format used by the wrappers.
+pub(crate) struct StateWriter<'a>(&'a mut [u8]);
+
+impl<'a> StateWriter<'a> {
+    /// Check that `out` has length of `size` and write the format version.
+    pub(crate) fn new(out: &'a mut [u8], size: usize) -> Result<Self, InvalidBufferSize> {
+        if out.len() != size {
+            return Err(InvalidBufferSize);
+        }
+        let mut w = Self(out);
+        w.write(&[STATE_VERSION]);
+        Ok(w)
+    }
+
+    pub(crate) fn write(&mut self, data: &[u8]) {
+        let (head, tail) = core::mem::take(&mut self.0).split_at_mut(data.len());
+        head.copy_from_slice(data);
+        self.0 = tail;
+    }
+
+    pub(crate) fn write_u64(&mut self, val: usize) {
+        self.write(&(val as u64).to_le_bytes());
+    }
+
+    /// Write core state.
+    pub(crate) fn write_core<T: SerializableStateCore>(&mut self, core: &T) {
+        self.write(core.serialize_state_core().as_ref());
+    }
+
+    /// Write buffered data, which is padded with zeros to the block size.
+    pub(crate) fn write_buffer<B: Array, K: BufferKind>(&mut self, buffer: &BlockBuffer<B, K>) {
+        let data = buffer.get_data();
+        let mut block = B::zero();
+        block.as_mut()[..data.len()].copy_from_slice(data);
+        self.write_u64(data.len());
+        self.write(block.as_ref());
+    }
+}
+
+/// Reader of the versioned state format used by the wrappers.
+pub(crate) struct StateReader<'a>(&'a [u8]);
+
+impl<'a> StateReader<'a> {
+    /// Check that `state` has length of `size` and a supported format version.
+    pub(crate) fn new(state: &'a [u8], size: usize) -> Result<Self, InvalidState> {
+        if state.len() != size {
+            return Err(InvalidState);
+        }
+        let mut r = Self(state);
+        match r.read(1) {
+            [STATE_VERSION] => Ok(r),
+            _ => Err(InvalidState),
+        }
+    }
+
+    pub(crate) fn read(&mut self, n: usize) -> &'a [u8] {
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        head
+    }
+
+    pub(crate) fn read_u64(&mut self) -> Result<usize, InvalidState> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.read(8));
+        usize::try_from(u64::from_le_bytes(buf)).map_err(|_| InvalidState)
+    }
+
+    /// Read core state.
+    pub(crate) fn read_core<T: SerializableStateCore>(&mut self) -> Result<T, InvalidState> {
+        let state = T::SerializedState::try_copy_from_slice(self.read(T::SerializedState::LEN))
+            .map_err(|_| InvalidState)?;
+        T::deserialize_state_core(&state)
+    }
+
+    /// Read buffer written by [`StateWriter::write_buffer`].
+    ///
+    /// Padding bytes after the buffered data must be equal to zero.
+    pub(crate) fn read_buffer<B: Array, K: BufferKind>(
+        &mut self,
+    ) -> Result<BlockBuffer<B, K>, InvalidState> {
+        let pos = self.read_u64()?;
+        let block = self.read(B::LEN);
+        if pos > block.len() {
+            return Err(InvalidState);
+        }
+        let (data, padding) = block.split_at(pos);
+        if padding.iter().any(|&b| b != 0) {
+            return Err(InvalidState);
+        }
+        BlockBuffer::try_new(data).map_err(|_| InvalidState)
+    }
+}
+
 /// Type which used for defining truncation side in the [`VariableOutputCore`]
 /// trait.
 #[derive(Copy, Clone, Debug)]
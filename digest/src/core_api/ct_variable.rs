@@ -1,11 +1,14 @@
 use super::{
-    AlgorithmName, Buffer, BufferKindUser, FixedOutputCore, Reset, TruncSide, UpdateCore,
-    VariableOutputCore,
+    find_metadata, AlgorithmInfo, AlgorithmMetadata, AlgorithmName, Buffer, BufferKindUser,
+    FixedOutputCore, Reset, SerializableStateCore, TruncSide, UpdateCore, VariableOutputCore,
+    VariableOutputMetadata,
 };
-use crate::HashMarker;
 #[cfg(feature = "mac")]
 use crate::MacMarker;
+use crate::{HashMarker, InvalidState};
 use array::Array;
+#[cfg(feature = "zeroize")]
+use block_buffer::zeroize::{Zeroize, ZeroizeOnDrop};
 use core::{fmt, marker::PhantomData};
 use crypto_common::{BlockSizeUser, OutputSizeUser};
 
@@ -112,3 +115,52 @@ where
         write!(f, "{}", OUT_SIZE)
     }
 }
+
+impl<T, const OUT_SIZE: usize> AlgorithmMetadata for CtVariableCoreWrapper<T, OUT_SIZE>
+where
+    T: VariableOutputMetadata,
+{
+    const METADATA: AlgorithmInfo = match find_metadata(T::OUTPUT_METADATA, OUT_SIZE) {
+        Some(info) => info,
+        None => panic!("algorithm metadata is not defined for the selected output size"),
+    };
+}
+
+impl<T, const OUT_SIZE: usize> SerializableStateCore for CtVariableCoreWrapper<T, OUT_SIZE>
+where
+    T: VariableOutputCore + SerializableStateCore,
+{
+    type SerializedState = T::SerializedState;
+
+    #[inline]
+    fn serialize_state_core(&self) -> Self::SerializedState {
+        self.inner.serialize_state_core()
+    }
+
+    #[inline]
+    fn deserialize_state_core(state: &Self::SerializedState) -> Result<Self, InvalidState> {
+        T::deserialize_state_core(state).map(|inner| Self {
+            inner,
+            _out: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<T, const OUT_SIZE: usize> ZeroizeOnDrop for CtVariableCoreWrapper<T, OUT_SIZE> where
+    T: VariableOutputCore + ZeroizeOnDrop
+{
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<T, const OUT_SIZE: usize> Zeroize for CtVariableCoreWrapper<T, OUT_SIZE>
+where
+    T: VariableOutputCore + Zeroize,
+{
+    #[inline]
+    fn zeroize(&mut self) {
+        self.inner.zeroize();
+    }
+}
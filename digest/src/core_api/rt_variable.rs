@@ -1,9 +1,14 @@
-use super::{AlgorithmName, TruncSide, UpdateCore, VariableOutputCore};
+use super::{
+    buffer_state_size, find_metadata, AlgorithmInfo, AlgorithmName, SerializableStateCore,
+    StateReader, StateWriter, TruncSide, UpdateCore, VariableOutputCore, VariableOutputMetadata,
+};
 #[cfg(feature = "mac")]
 use crate::MacMarker;
-use crate::{HashMarker, InvalidBufferSize};
+use crate::{HashMarker, InvalidBufferSize, InvalidState, SerializableState};
 use crate::{InvalidOutputSize, Reset, Update, VariableOutput, VariableOutputReset};
 use array::Array;
+#[cfg(feature = "zeroize")]
+use block_buffer::zeroize::{Zeroize, ZeroizeOnDrop};
 use block_buffer::BlockBuffer;
 use core::fmt;
 
@@ -45,17 +50,24 @@ where
     }
 }
 
+impl<T> RtVariableCoreWrapper<T>
+where
+    T: VariableOutputMetadata,
+{
+    /// Get metadata for the selected output size.
+    ///
+    /// Returns `None` if the core does not define metadata for it.
+    #[inline]
+    pub fn metadata(&self) -> Option<AlgorithmInfo> {
+        find_metadata(T::OUTPUT_METADATA, self.output_size)
+    }
+}
+
 impl<T> HashMarker for RtVariableCoreWrapper<T> where T: VariableOutputCore + HashMarker {}
 
 #[cfg(feature = "mac")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mac")))]
-impl<T> MacMarker for RtVariableCoreWrapper<T>
-where
-    T: VariableOutputCore + MacMarker,
-    T::BlockSize: IsLess<U256>,
-    Le<T::BlockSize, U256>: NonZero,
-{
-}
+impl<T> MacMarker for RtVariableCoreWrapper<T> where T: VariableOutputCore + MacMarker {}
 
 impl<T> Reset for RtVariableCoreWrapper<T>
 where
@@ -115,6 +127,37 @@ where
     }
 }
 
+impl<T> SerializableState for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + SerializableStateCore,
+{
+    const SERIALIZED_STATE_SIZE: usize =
+        1 + 8 + <T::SerializedState as Array>::LEN + buffer_state_size(<T::Block as Array>::LEN);
+
+    fn serialize_state(&self, out: &mut [u8]) -> Result<(), InvalidBufferSize> {
+        let mut w = StateWriter::new(out, Self::SERIALIZED_STATE_SIZE)?;
+        w.write_u64(self.output_size);
+        w.write_core(&self.core);
+        w.write_buffer(&self.buffer);
+        Ok(())
+    }
+
+    fn deserialize_state(state: &[u8]) -> Result<Self, InvalidState> {
+        let mut r = StateReader::new(state, Self::SERIALIZED_STATE_SIZE)?;
+        let output_size = r.read_u64()?;
+        if output_size > Self::MAX_OUTPUT_SIZE || T::new(output_size).is_err() {
+            return Err(InvalidState);
+        }
+        let core = r.read_core()?;
+        let buffer = r.read_buffer()?;
+        Ok(Self {
+            core,
+            buffer,
+            output_size,
+        })
+    }
+}
+
 impl<T> fmt::Debug for RtVariableCoreWrapper<T>
 where
     T: VariableOutputCore + UpdateCore + AlgorithmName,
@@ -142,3 +185,20 @@ where
         Ok(())
     }
 }
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<T> ZeroizeOnDrop for RtVariableCoreWrapper<T> where T: VariableOutputCore + ZeroizeOnDrop {}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<T> Zeroize for RtVariableCoreWrapper<T>
+where
+    T: VariableOutputCore + UpdateCore + Zeroize,
+{
+    #[inline]
+    fn zeroize(&mut self) {
+        self.core.zeroize();
+        self.buffer.zeroize();
+    }
+}
@@ -1,16 +1,25 @@
+#[cfg(feature = "alloc")]
+use super::ParUpdateCore;
 use super::{
-    AlgorithmName, Buffer, BufferKindUser, ExtendableOutputCore, FixedOutputCore, OutputSizeUser,
-    Reset, UpdateCore, XofReaderCoreWrapper,
+    buffer_state_size, AlgorithmInfo, AlgorithmMetadata, AlgorithmName, Buffer, BufferKindUser,
+    ExtendableOutputCore, FixedOutputCore, OutputSizeUser, Reset, SerializableStateCore,
+    StateReader, StateWriter, UpdateCore, XofReaderCoreWrapper,
 };
 use crate::{
-    ExtendableOutput, ExtendableOutputReset, FixedOutput, FixedOutputReset, HashMarker, Update,
+    ExtendableOutput, ExtendableOutputReset, FixedOutput, FixedOutputReset, HashMarker,
+    InvalidBufferSize, InvalidState, SerializableState, Update,
 };
+use array::Array;
+#[cfg(feature = "zeroize")]
+use block_buffer::zeroize::{Zeroize, ZeroizeOnDrop};
 use block_buffer::BlockBuffer;
 use core::fmt;
-use crypto_common::{BlockSizeUser, KeyInit, KeySizeUser};
+use crypto_common::{BlockSizeUser, InvalidLength, KeyInit, KeySizeUser, Lengths};
 
 #[cfg(feature = "mac")]
 use crate::MacMarker;
+#[cfg(feature = "alloc")]
+use {crate::BatchDigest, alloc::vec::Vec};
 
 /// Wrapper around [`BufferKindUser`].
 ///
@@ -71,6 +80,7 @@ where
     T: BufferKindUser + KeySizeUser,
 {
     type Key = T::Key;
+    const KEY_SIZES: Lengths = T::KEY_SIZES;
 }
 
 impl<T> KeyInit for CoreWrapper<T>
@@ -86,7 +96,7 @@ where
     }
 
     #[inline]
-    fn new_from_slice(key: &[u8]) -> Result<Self, core::array::TryFromSliceError> {
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
         Ok(Self {
             core: T::new_from_slice(key)?,
             buffer: Default::default(),
@@ -94,6 +104,23 @@ where
     }
 }
 
+impl<T> AlgorithmMetadata for CoreWrapper<T>
+where
+    T: BufferKindUser + AlgorithmMetadata,
+{
+    const METADATA: AlgorithmInfo = T::METADATA;
+}
+
+impl<T> AlgorithmName for CoreWrapper<T>
+where
+    T: BufferKindUser + AlgorithmName,
+{
+    #[inline]
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::write_alg_name(f)
+    }
+}
+
 impl<T> fmt::Debug for CoreWrapper<T>
 where
     T: BufferKindUser + AlgorithmName,
@@ -191,6 +218,72 @@ where
     }
 }
 
+impl<T> SerializableState for CoreWrapper<T>
+where
+    T: BufferKindUser + SerializableStateCore,
+{
+    const SERIALIZED_STATE_SIZE: usize =
+        1 + <T::SerializedState as Array>::LEN + buffer_state_size(<T::Block as Array>::LEN);
+
+    fn serialize_state(&self, out: &mut [u8]) -> Result<(), InvalidBufferSize> {
+        let mut w = StateWriter::new(out, Self::SERIALIZED_STATE_SIZE)?;
+        w.write_core(&self.core);
+        w.write_buffer(&self.buffer);
+        Ok(())
+    }
+
+    fn deserialize_state(state: &[u8]) -> Result<Self, InvalidState> {
+        let mut r = StateReader::new(state, Self::SERIALIZED_STATE_SIZE)?;
+        let core = r.read_core()?;
+        let buffer = r.read_buffer()?;
+        Ok(Self { core, buffer })
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<T> BatchDigest for CoreWrapper<T>
+where
+    T: ParUpdateCore + FixedOutputCore + Default + HashMarker,
+{
+    fn digest_batch_into(messages: &[&[u8]], out: &mut [Self::Output]) {
+        assert_eq!(messages.len(), out.len(), "batch output length mismatch");
+
+        let splits: Vec<_> = messages
+            .iter()
+            .map(|m| Buffer::<T>::split_blocks(m))
+            .collect();
+        // sort messages by number of blocks in descending order, so lanes
+        // which are still active always form a prefix of a group
+        let mut order: Vec<usize> = (0..messages.len()).collect();
+        order.sort_unstable_by_key(|&i| core::cmp::Reverse(splits[i].0.len()));
+
+        let lanes = core::cmp::max(T::PAR_STATES, 1);
+        let mut states: Vec<T> = Vec::with_capacity(lanes);
+        let mut blocks: Vec<T::Block> = Vec::with_capacity(lanes);
+        for group in order.chunks(lanes) {
+            states.clear();
+            states.resize_with(group.len(), Default::default);
+
+            let max_blocks = splits[group[0]].0.len();
+            let mut active = group.len();
+            for n in 0..max_blocks {
+                while splits[group[active - 1]].0.len() <= n {
+                    active -= 1;
+                }
+                blocks.clear();
+                blocks.extend(group[..active].iter().map(|&i| splits[i].0[n]));
+                T::update_par_blocks(&mut states[..active], &blocks);
+            }
+
+            for (state, &i) in states.iter_mut().zip(group) {
+                let mut buffer = Buffer::<T>::new(splits[i].1);
+                state.finalize_fixed_core(&mut buffer, &mut out[i]);
+            }
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl<T> std::io::Write for CoreWrapper<T>
@@ -229,3 +322,20 @@ where
 {
     type Core = T;
 }
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<T> ZeroizeOnDrop for CoreWrapper<T> where T: BufferKindUser + ZeroizeOnDrop {}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<T> Zeroize for CoreWrapper<T>
+where
+    T: BufferKindUser + Zeroize,
+{
+    #[inline]
+    fn zeroize(&mut self) {
+        self.core.zeroize();
+        self.buffer.zeroize();
+    }
+}
@@ -1,18 +1,29 @@
-use super::{AlgorithmName, XofReaderCore};
-use crate::XofReader;
-use block_buffer::EagerBuffer;
+use super::{
+    buffer_state_size, AlgorithmName, SerializableStateCore, StateReader, StateWriter,
+    XofReaderCore, XofReaderSeekCore,
+};
+use crate::{
+    InvalidBufferSize, InvalidPosition, InvalidState, SerializableState, XofReader, XofReaderSeek,
+};
+use array::Array;
+#[cfg(feature = "zeroize")]
+use block_buffer::zeroize::{Zeroize, ZeroizeOnDrop};
+use block_buffer::ReadBuffer;
+use core::convert::TryFrom;
 use core::fmt;
 
 /// Wrapper around [`XofReaderCore`] implementations.
 ///
 /// It handles data buffering and implements the mid-level traits.
+/// Cursor of the buffer is stored as [`u16`], so blocks of up to
+/// 65536 bytes are supported.
 pub struct XofReaderCoreWrapper<T: XofReaderCore> {
     pub(super) core: T,
-    pub(super) buffer: EagerBuffer<T::Block>,
+    pub(super) buffer: ReadBuffer<T::Block, u16>,
 }
 impl<T: XofReaderCore + Clone> Clone for XofReaderCoreWrapper<T>
 where
-    EagerBuffer<T::Block>: Clone,
+    ReadBuffer<T::Block, u16>: Clone,
 {
     fn clone(&self) -> Self {
         Self {
@@ -23,7 +34,7 @@ where
 }
 impl<T: XofReaderCore + Default> Default for XofReaderCoreWrapper<T>
 where
-    EagerBuffer<T::Block>: Default,
+    ReadBuffer<T::Block, u16>: Default,
 {
     fn default() -> Self {
         Self {
@@ -44,11 +55,79 @@ impl<T: XofReaderCore> XofReader for XofReaderCoreWrapper<T> {
     #[inline]
     fn read(&mut self, buffer: &mut [u8]) {
         let Self { core, buffer: buf } = self;
-        buf.set_data(buffer, |blocks| {
-            for block in blocks {
-                *block = core.read_block();
-            }
-        });
+        buf.read(buffer, |block| *block = core.read_block());
+    }
+}
+
+impl<T: XofReaderSeekCore> XofReaderSeek for XofReaderCoreWrapper<T> {
+    fn try_current_pos(&self) -> Result<u64, InvalidPosition> {
+        let Self { core, buffer } = self;
+        let mut block_pos = core.get_block_pos();
+        let byte_pos = buffer.get_pos();
+        // cached block was already counted by the core
+        if byte_pos != 0 {
+            block_pos = block_pos.checked_sub(1).ok_or(InvalidPosition)?;
+        }
+        let bs = u64::try_from(T::Block::LEN).map_err(|_| InvalidPosition)?;
+        block_pos
+            .checked_mul(bs)
+            .and_then(|pos| pos.checked_add(byte_pos as u64))
+            .ok_or(InvalidPosition)
+    }
+
+    fn try_seek(&mut self, pos: u64) -> Result<(), InvalidPosition> {
+        let Self { core, buffer } = self;
+        let bs = u64::try_from(T::Block::LEN).map_err(|_| InvalidPosition)?;
+        let byte_pos = (pos % bs) as usize;
+        core.set_block_pos(pos / bs);
+        if byte_pos != 0 {
+            buffer.write_block(byte_pos, |block| *block = core.read_block(), |_| {});
+        } else {
+            buffer.reset();
+        }
+        Ok(())
+    }
+}
+
+impl<T> SerializableState for XofReaderCoreWrapper<T>
+where
+    T: XofReaderCore + SerializableStateCore,
+{
+    const SERIALIZED_STATE_SIZE: usize =
+        1 + <T::SerializedState as Array>::LEN + buffer_state_size(<T::Block as Array>::LEN);
+
+    fn serialize_state(&self, out: &mut [u8]) -> Result<(), InvalidBufferSize> {
+        let mut w = StateWriter::new(out, Self::SERIALIZED_STATE_SIZE)?;
+        w.write_core(&self.core);
+        // bytes before the cursor were already read, so only
+        // the unread part of the block is stored
+        let pos = self.buffer.get_pos();
+        let mut block = T::Block::zero();
+        if pos != 0 {
+            block.as_mut()[pos..].copy_from_slice(&self.buffer.get_block().as_ref()[pos..]);
+        }
+        w.write_u64(pos);
+        w.write(block.as_ref());
+        Ok(())
+    }
+
+    fn deserialize_state(state: &[u8]) -> Result<Self, InvalidState> {
+        let mut r = StateReader::new(state, Self::SERIALIZED_STATE_SIZE)?;
+        let core = r.read_core()?;
+        let pos = r.read_u64()?;
+        let block =
+            T::Block::try_copy_from_slice(r.read(T::Block::LEN)).map_err(|_| InvalidState)?;
+        if pos >= T::Block::LEN {
+            return Err(InvalidState);
+        }
+        // already read bytes and blocks without unread bytes are stored as zeros
+        let read = if pos == 0 { T::Block::LEN } else { pos };
+        if block.as_ref()[..read].iter().any(|&b| b != 0) {
+            return Err(InvalidState);
+        }
+        let mut buffer = ReadBuffer::default();
+        buffer.try_set(block, pos).map_err(|_| InvalidState)?;
+        Ok(Self { core, buffer })
     }
 }
 
@@ -61,3 +140,20 @@ impl<T: XofReaderCore> std::io::Read for XofReaderCoreWrapper<T> {
         Ok(buf.len())
     }
 }
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<T> ZeroizeOnDrop for XofReaderCoreWrapper<T> where T: XofReaderCore + ZeroizeOnDrop {}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<T> Zeroize for XofReaderCoreWrapper<T>
+where
+    T: XofReaderCore + Zeroize,
+{
+    #[inline]
+    fn zeroize(&mut self) {
+        self.core.zeroize();
+        self.buffer.zeroize();
+    }
+}
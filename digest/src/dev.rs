@@ -3,13 +3,14 @@
 pub use blobby;
 
 mod fixed;
+mod kdf;
 mod mac;
 mod rng;
 mod variable;
 mod xof;
 
 pub use fixed::*;
-pub use mac::*;
+pub use kdf::*;
 pub use variable::*;
 pub use xof::*;
 
@@ -39,6 +40,37 @@ macro_rules! new_test {
     };
 }
 
+/// Define key derivation function test
+///
+/// Test vectors are stored as rows of input keying material, salt, info
+/// and output keying material, which must not be longer than 1024 bytes.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "dev")))]
+macro_rules! new_kdf_test {
+    ($name:ident, $test_name:expr, $kdf:ty $(,)?) => {
+        #[test]
+        fn $name() {
+            use digest::dev::blobby::Blob4Iterator;
+            let data = include_bytes!(concat!("data/", $test_name, ".blb"));
+
+            for (i, row) in Blob4Iterator::new(data).unwrap().enumerate() {
+                let [ikm, salt, info, okm] = row.unwrap();
+                if let Some(desc) = digest::dev::kdf_test::<$kdf>(ikm, salt, info, okm) {
+                    panic!(
+                        "\n\
+                         Failed test №{}: {}\n\
+                         ikm:\t{:?}\n\
+                         salt:\t{:?}\n\
+                         info:\t{:?}\n\
+                         okm:\t{:?}\n",
+                        i, desc, ikm, salt, info, okm,
+                    );
+                }
+            }
+        }
+    };
+}
+
 /// Define [`Update`][crate::Update] impl benchmark
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "dev")))]
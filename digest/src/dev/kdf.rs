@@ -0,0 +1,41 @@
+use crate::{Info, Kdf};
+
+/// Key derivation function test
+pub fn kdf_test<K: Kdf>(ikm: &[u8], salt: &[u8], info: &[u8], okm: &[u8]) -> Option<&'static str> {
+    let mut buf = [0u8; 1024];
+    if okm.len() > buf.len() {
+        return Some("output of test vector is too long");
+    }
+    let buf = &mut buf[..okm.len()];
+
+    if K::derive(salt, ikm, &[info], buf).is_err() || buf != okm {
+        return Some("derive");
+    }
+    buf.iter_mut().for_each(|b| *b = 0);
+
+    let kdf = K::extract(salt, ikm);
+    if kdf.expand(&[info], buf).is_err() || buf != okm {
+        return Some("extract and expand");
+    }
+    buf.iter_mut().for_each(|b| *b = 0);
+
+    // Test that info split into several parts is processed as concatenated
+    for i in 0..=info.len() {
+        let (a, b) = info.split_at(i);
+        let parts = Info::new().part(a).part(b);
+        if kdf.expand(&parts, buf).is_err() || buf != okm {
+            return Some("info in parts");
+        }
+        buf.iter_mut().for_each(|b| *b = 0);
+    }
+
+    // Test that outputs longer than the limit are rejected
+    if K::MAX_OUTPUT_SIZE < 1024 {
+        let mut buf = [0u8; 1024];
+        if kdf.expand(&[info], &mut buf[..=K::MAX_OUTPUT_SIZE]).is_ok() {
+            return Some("output size limit");
+        }
+    }
+
+    None
+}
@@ -49,3 +49,61 @@ where
 
     None
 }
+
+/// Define test of XOF reader seeking capabilities
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "dev")))]
+macro_rules! xof_seek_test {
+    ($name:ident, $hasher:ty $(,)?) => {
+        #[test]
+        fn $name() {
+            use digest::{ExtendableOutput, Update, XofReader, XofReaderSeek};
+
+            fn get_reader() -> <$hasher as ExtendableOutput>::Reader {
+                let mut hasher = <$hasher>::default();
+                hasher.update(b"xof seek test");
+                hasher.finalize_xof()
+            }
+
+            const MAX_SEEK: usize = 512;
+
+            let mut out = [0u8; MAX_SEEK];
+            get_reader().read(&mut out);
+
+            for n in 0..MAX_SEEK {
+                let mut reader = get_reader();
+                assert_eq!(reader.current_pos(), 0);
+                reader.seek(n as u64);
+                assert_eq!(reader.current_pos(), n as u64);
+                let mut buf = [0u8; MAX_SEEK];
+                reader.read(&mut buf[n..]);
+                assert_eq!(reader.current_pos(), MAX_SEEK as u64);
+                assert_eq!(&buf[n..], &out[n..]);
+
+                // seek backwards after reading
+                reader.seek(n as u64 / 2);
+                reader.read(&mut buf[n / 2..]);
+                assert_eq!(&buf[n / 2..], &out[n / 2..]);
+            }
+
+            const MAX_CHUNK: usize = 128;
+            const MAX_LEN: usize = 1024;
+
+            let mut buf = [0u8; MAX_CHUNK];
+            let mut reader = get_reader();
+            reader.read(&mut []);
+            assert_eq!(reader.current_pos(), 0);
+            for n in 1..MAX_CHUNK {
+                assert_eq!(reader.current_pos(), 0);
+                for m in 1.. {
+                    reader.read(&mut buf[..n]);
+                    assert_eq!(reader.current_pos(), (n * m) as u64);
+                    if n * m > MAX_LEN {
+                        break;
+                    }
+                }
+                reader.seek(0);
+            }
+        }
+    };
+}
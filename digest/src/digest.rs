@@ -1,9 +1,14 @@
-use super::{FixedOutput, FixedOutputReset, InvalidBufferSize, Reset, Update};
+use super::{
+    ExtendableOutputReset, FixedOutput, FixedOutputReset, InvalidBufferSize, Reset, Update,
+    VariableOutput, VariableOutputReset,
+};
 use array::Array;
 use crypto_common::OutputSizeUser;
 
 #[cfg(feature = "alloc")]
-use alloc::boxed::Box;
+use super::{ExtendableOutput, XofReader};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
 
 /// Marker trait for cryptographic hash functions.
 pub trait HashMarker {}
@@ -237,3 +242,186 @@ impl Clone for Box<dyn DynDigest> {
         self.box_clone()
     }
 }
+
+/// Modification of the [`ExtendableOutputReset`] trait suitable for
+/// trait objects.
+///
+/// Readers are returned as boxed [`XofReader`] trait objects.
+pub trait DynExtendableOutput {
+    /// Digest input data.
+    ///
+    /// This method can be called repeatedly for use with streaming messages.
+    fn update(&mut self, data: &[u8]);
+
+    /// Retrieve XOF reader and consume boxed hasher instance.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn finalize_xof(self: Box<Self>) -> Box<dyn XofReader>;
+
+    /// Retrieve XOF reader and reset hasher instance.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn finalize_xof_reset(&mut self) -> Box<dyn XofReader>;
+
+    /// Finalize XOF, write result into `out`, and reset the hasher instance.
+    fn finalize_xof_reset_into(&mut self, out: &mut [u8]);
+
+    /// Reset hasher instance to its initial state.
+    fn reset(&mut self);
+
+    /// Clone hasher state into a boxed trait object
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn box_clone(&self) -> Box<dyn DynExtendableOutput>;
+}
+
+impl<D> DynExtendableOutput for D
+where
+    D: ExtendableOutputReset + Clone + 'static,
+    D::Reader: 'static,
+{
+    fn update(&mut self, data: &[u8]) {
+        Update::update(self, data);
+    }
+
+    #[cfg(feature = "alloc")]
+    fn finalize_xof(self: Box<Self>) -> Box<dyn XofReader> {
+        Box::new(ExtendableOutput::finalize_xof(*self))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn finalize_xof_reset(&mut self) -> Box<dyn XofReader> {
+        Box::new(ExtendableOutputReset::finalize_xof_reset(self))
+    }
+
+    fn finalize_xof_reset_into(&mut self, out: &mut [u8]) {
+        ExtendableOutputReset::finalize_xof_reset_into(self, out);
+    }
+
+    fn reset(&mut self) {
+        Reset::reset(self);
+    }
+
+    #[cfg(feature = "alloc")]
+    fn box_clone(&self) -> Box<dyn DynExtendableOutput> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl Clone for Box<dyn DynExtendableOutput> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// Modification of the [`VariableOutputReset`] trait suitable for
+/// trait objects.
+pub trait DynVariableOutput {
+    /// Digest input data.
+    ///
+    /// This method can be called repeatedly for use with streaming messages.
+    fn update(&mut self, data: &[u8]);
+
+    /// Retrieve result and consume boxed hasher instance.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn finalize_boxed(self: Box<Self>) -> Box<[u8]>;
+
+    /// Retrieve result and reset hasher instance.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn finalize_boxed_reset(&mut self) -> Box<[u8]> {
+        let mut result = vec![0; self.output_size()];
+        self.finalize_variable_reset(&mut result).unwrap();
+        result.into_boxed_slice()
+    }
+
+    /// Write result into provided buffer and reset the hasher instance.
+    ///
+    /// Returns error if buffer length is not equal to `output_size`.
+    fn finalize_variable_reset(&mut self, out: &mut [u8]) -> Result<(), InvalidBufferSize>;
+
+    /// Reset hasher instance to its initial state.
+    fn reset(&mut self);
+
+    /// Get output size of the hasher instance.
+    fn output_size(&self) -> usize;
+
+    /// Get maximum output size supported by the hasher.
+    fn max_output_size(&self) -> usize;
+
+    /// Clone hasher state into a boxed trait object
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn box_clone(&self) -> Box<dyn DynVariableOutput>;
+}
+
+impl<D: VariableOutputReset + Clone + 'static> DynVariableOutput for D {
+    fn update(&mut self, data: &[u8]) {
+        Update::update(self, data);
+    }
+
+    #[cfg(feature = "alloc")]
+    fn finalize_boxed(self: Box<Self>) -> Box<[u8]> {
+        VariableOutput::finalize_boxed(*self)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn finalize_boxed_reset(&mut self) -> Box<[u8]> {
+        VariableOutputReset::finalize_boxed_reset(self)
+    }
+
+    fn finalize_variable_reset(&mut self, out: &mut [u8]) -> Result<(), InvalidBufferSize> {
+        VariableOutputReset::finalize_variable_reset(self, out)
+    }
+
+    fn reset(&mut self) {
+        Reset::reset(self);
+    }
+
+    fn output_size(&self) -> usize {
+        VariableOutput::output_size(self)
+    }
+
+    fn max_output_size(&self) -> usize {
+        D::MAX_OUTPUT_SIZE
+    }
+
+    #[cfg(feature = "alloc")]
+    fn box_clone(&self) -> Box<dyn DynVariableOutput> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl Clone for Box<dyn DynVariableOutput> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+/// Hash functions able to hash batches of independent messages, e.g. by
+/// processing several messages in parallel using SIMD.
+///
+/// Implemented by [`CoreWrapper`][crate::core_api::CoreWrapper] for cores
+/// which implement [`ParUpdateCore`][crate::core_api::ParUpdateCore].
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub trait BatchDigest: OutputSizeUser {
+    /// Compute hash of every message in `messages` and write it into
+    /// the element of `out` with the same index.
+    ///
+    /// # Panics
+    /// If length of `out` is not equal to length of `messages`.
+    fn digest_batch_into(messages: &[&[u8]], out: &mut [Self::Output]);
+
+    /// Compute hash of every message in `messages`.
+    fn digest_batch(messages: &[&[u8]]) -> Vec<Self::Output> {
+        let mut out = vec![Self::Output::zero(); messages.len()];
+        Self::digest_batch_into(messages, &mut out);
+        out
+    }
+}
@@ -0,0 +1,151 @@
+//! HMAC-based Extract-and-Expand Key Derivation Function (HKDF)
+//! defined in [RFC 5869].
+//!
+//! [`Hkdf`] is generic over the MAC, which should be an HMAC instance,
+//! e.g. `Hkdf<Hmac<Sha256>>` or `Hkdf<SimpleHmac<Sha256>>`
+//! (see the [`hmac`][crate::hmac] module).
+//!
+//! ```
+//! use digest::hkdf::Hkdf;
+//! use digest::hmac::SimpleHmac;
+//! use digest::{Info, Kdf};
+//! # use digest::{FixedOutput, HashMarker, OutputSizeUser};
+//! # use digest::crypto_common::BlockSizeUser;
+//! # #[derive(Clone, Default)]
+//! # struct Sha256(u32);
+//! # impl HashMarker for Sha256 {}
+//! # impl BlockSizeUser for Sha256 { type Block = [u8; 64]; }
+//! # impl OutputSizeUser for Sha256 { type Output = [u8; 4]; }
+//! # impl digest::Update for Sha256 {
+//! #     fn update(&mut self, data: &[u8]) {
+//! #         for &b in data { self.0 = self.0.rotate_left(5) ^ u32::from(b) }
+//! #     }
+//! # }
+//! # impl FixedOutput for Sha256 {
+//! #     fn finalize_into(self, out: &mut [u8; 4]) { *out = self.0.to_le_bytes() }
+//! # }
+//!
+//! type HkdfSha256 = Hkdf<SimpleHmac<Sha256>>;
+//!
+//! let shared_secret = [0x42; 32];
+//! let hkdf = HkdfSha256::extract(b"salt", &shared_secret);
+//!
+//! let info = Info::new().part(b"my protocol").part(b"client key");
+//! let client_key: [u8; 32] = hkdf.expand_array(&info).unwrap();
+//!
+//! let mut server_key = [0u8; 48];
+//! hkdf.expand(&[&b"my protocol"[..], &b"server key"[..]], &mut server_key).unwrap();
+//!
+//! // output is limited to 255 MAC outputs
+//! let mut too_long = [0u8; 255 * 4 + 1];
+//! assert!(hkdf.expand(&info, &mut too_long).is_err());
+//! ```
+//!
+//! [RFC 5869]: https://www.rfc-editor.org/rfc/rfc5869
+
+use crate::{InvalidOutputSize, Kdf, Mac};
+use array::Array;
+use core::fmt;
+use crypto_common::{InvalidLength, KeyInit, Lengths, Output};
+
+/// HKDF instance holding a pseudorandom key.
+///
+/// `M` must accept keys of any length like HMAC does, i.e. its
+/// [`KEY_SIZES`][crypto_common::KeySizeUser::KEY_SIZES] must be equal to
+/// [`Lengths::ANY`], otherwise compilation fails.
+#[derive(Clone)]
+pub struct Hkdf<M: Mac + KeyInit + Clone> {
+    prk_mac: M,
+}
+
+impl<M: Mac + KeyInit + Clone> Hkdf<M> {
+    /// Size of the pseudorandom key in bytes.
+    pub const PRK_SIZE: usize = <Output<M> as Array>::LEN;
+
+    const KEY_SIZES_CHECK: () = assert!(
+        matches!(
+            M::KEY_SIZES,
+            Lengths::Range {
+                min: 0,
+                max: usize::MAX,
+                step: 1,
+            }
+        ),
+        "HKDF requires MAC which accepts keys of any size"
+    );
+
+    /// Initialize MAC with `key` of any size.
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    fn new_mac(key: &[u8]) -> M {
+        let () = Self::KEY_SIZES_CHECK;
+        match <M as KeyInit>::new_from_slice(key) {
+            Ok(mac) => mac,
+            Err(_) => unreachable!("MAC must accept keys of any size"),
+        }
+    }
+
+    /// Extract pseudorandom key from input keying material `ikm` and return
+    /// it together with the HKDF instance.
+    ///
+    /// Empty `salt` is equivalent to absence of salt.
+    pub fn extract_prk(salt: &[u8], ikm: &[u8]) -> (Output<M>, Self) {
+        let mut mac = Self::new_mac(salt);
+        Mac::update(&mut mac, ikm);
+        let prk = mac.finalize().into_bytes();
+        let prk_mac = Self::new_mac(prk.as_ref());
+        (prk, Self { prk_mac })
+    }
+
+    /// Create HKDF instance from an already extracted pseudorandom key,
+    /// skipping the extract step.
+    ///
+    /// Returns an error if `prk` is shorter than
+    /// [`PRK_SIZE`][Self::PRK_SIZE].
+    pub fn from_prk(prk: &[u8]) -> Result<Self, InvalidLength> {
+        if prk.len() < Self::PRK_SIZE {
+            let expected = Lengths::range(Self::PRK_SIZE, usize::MAX, 1);
+            return Err(InvalidLength::key(expected, prk.len()));
+        }
+        Ok(Self {
+            prk_mac: Self::new_mac(prk),
+        })
+    }
+}
+
+impl<M: Mac + KeyInit + Clone> Kdf for Hkdf<M> {
+    const MAX_OUTPUT_SIZE: usize = 255 * <Output<M> as Array>::LEN;
+
+    #[inline]
+    fn extract(salt: &[u8], ikm: &[u8]) -> Self {
+        Self::extract_prk(salt, ikm).1
+    }
+
+    fn expand(&self, info: &[&[u8]], okm: &mut [u8]) -> Result<(), InvalidOutputSize> {
+        if okm.len() > Self::MAX_OUTPUT_SIZE {
+            return Err(InvalidOutputSize);
+        }
+        let mut prev: Option<Output<M>> = None;
+        for (i, chunk) in okm.chunks_mut(<Output<M> as Array>::LEN).enumerate() {
+            let mut mac = self.prk_mac.clone();
+            if let Some(prev) = &prev {
+                Mac::update(&mut mac, prev.as_ref());
+            }
+            for part in info {
+                Mac::update(&mut mac, part);
+            }
+            // `i` is smaller than 255 because of the output size check
+            Mac::update(&mut mac, &[i as u8 + 1]);
+            let block = mac.finalize().into_bytes();
+            chunk.copy_from_slice(&block.as_ref()[..chunk.len()]);
+            prev = Some(block);
+        }
+        Ok(())
+    }
+}
+
+impl<M: Mac + KeyInit + Clone> fmt::Debug for Hkdf<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Hkdf { .. }")
+    }
+}
@@ -0,0 +1,421 @@
+//! Generic implementation of Hash-based Message Authentication Code (HMAC).
+//!
+//! Two implementations are provided:
+//!
+//! - [`Hmac`] works over hash functions implemented as [`CoreWrapper`]
+//!   around a block-level core. Hash states after processing of the padded
+//!   key blocks are precomputed during initialization, so processing of
+//!   every message does not include key blocks. Requires `core-api` feature.
+//! - [`SimpleHmac`] works over any hash function which implements
+//!   [`Digest`] and [`BlockSizeUser`] at the cost of storing padded key blocks
+//!   and processing them for every message.
+//!
+//! Both types implement [`Mac`][crate::Mac] and accept keys of any length.
+//!
+//! With enabled `zeroize` feature intermediate copies of the key are wiped
+//! after initialization and [`SimpleHmac`] wipes its padded key blocks on
+//! drop. [`HmacCore`] stores only hash states, so it implements
+//! `ZeroizeOnDrop` if the core of the hash function does.
+//!
+//! ```
+//! use digest::hmac::SimpleHmac;
+//! use digest::Mac;
+//! # use digest::{FixedOutput, HashMarker, OutputSizeUser};
+//! # use digest::crypto_common::BlockSizeUser;
+//! # #[derive(Clone, Default)]
+//! # struct Sha256(u32);
+//! # impl HashMarker for Sha256 {}
+//! # impl BlockSizeUser for Sha256 { type Block = [u8; 64]; }
+//! # impl OutputSizeUser for Sha256 { type Output = [u8; 4]; }
+//! # impl digest::Update for Sha256 {
+//! #     fn update(&mut self, data: &[u8]) {
+//! #         for &b in data { self.0 = self.0.rotate_left(5) ^ u32::from(b) }
+//! #     }
+//! # }
+//! # impl FixedOutput for Sha256 {
+//! #     fn finalize_into(self, out: &mut [u8; 4]) { *out = self.0.to_le_bytes() }
+//! # }
+//!
+//! let mut mac = SimpleHmac::<Sha256>::new_from_slice(b"my secret key").unwrap();
+//! mac.update(b"input message");
+//! let tag = mac.finalize().into_bytes();
+//!
+//! let mut mac = SimpleHmac::<Sha256>::new_from_slice(b"my secret key").unwrap();
+//! mac.update(b"input message");
+//! assert!(mac.verify(&tag).is_ok());
+//! ```
+
+use crate::{Digest, FixedOutput, FixedOutputReset, MacMarker, Update};
+use array::Array;
+use core::fmt;
+use crypto_common::{
+    Block, BlockSizeUser, InvalidLength, KeyInit, KeySizeUser, Lengths, OutputSizeUser, Reset,
+};
+
+#[cfg(feature = "zeroize")]
+use block_buffer::zeroize::{Zeroize, ZeroizeOnDrop};
+
+#[cfg(feature = "core-api")]
+use crate::{
+    core_api::{
+        AlgorithmName, Buffer, BufferKindUser, CoreProxy, CoreWrapper, FixedOutputCore, UpdateCore,
+    },
+    HashMarker,
+};
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5C;
+
+/// Derive key block from `key`: keys longer than the block size are hashed,
+/// shorter keys are padded with zeros.
+fn get_der_key<D: Digest + BlockSizeUser>(key: &[u8]) -> Block<D> {
+    let mut der_key = Block::<D>::zero();
+    if key.len() <= <Block<D> as Array>::LEN {
+        der_key.as_mut()[..key.len()].copy_from_slice(key);
+    } else {
+        let mut hash = D::digest(key);
+        der_key.as_mut()[..hash.as_ref().len()].copy_from_slice(hash.as_ref());
+        wipe(&mut hash);
+    }
+    der_key
+}
+
+/// Return copy of `key` with every byte XORed with `pad`.
+#[inline]
+fn xor_key<B: Array>(key: &B, pad: u8) -> B {
+    B::from_fn(|i| key.as_ref()[i] ^ pad)
+}
+
+/// Wipe key material if `zeroize` feature is enabled.
+#[inline(always)]
+fn wipe<B: Array>(key: &mut B) {
+    #[cfg(feature = "zeroize")]
+    key.as_mut().zeroize();
+    #[cfg(not(feature = "zeroize"))]
+    let _ = key;
+}
+
+/// Generic HMAC over hash functions implemented using [`CoreWrapper`].
+#[cfg(feature = "core-api")]
+#[cfg_attr(docsrs, doc(cfg(feature = "core-api")))]
+pub type Hmac<D> = CoreWrapper<HmacCore<D>>;
+
+/// Block-level HMAC core over hash function `D` implemented using
+/// [`CoreWrapper`].
+///
+/// Usually it should be used via the [`Hmac`] type alias.
+#[cfg(feature = "core-api")]
+#[cfg_attr(docsrs, doc(cfg(feature = "core-api")))]
+pub struct HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+    digest: D::Core,
+    ipad_digest: D::Core,
+    opad_digest: D::Core,
+}
+
+#[cfg(feature = "core-api")]
+impl<D> HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+    const OUTPUT_SIZE_CHECK: () = assert!(
+        <<D::Core as OutputSizeUser>::Output as Array>::LEN
+            <= <<D::Core as BlockSizeUser>::Block as Array>::LEN,
+        "HMAC requires hash output size not bigger than its block size"
+    );
+
+    #[allow(clippy::let_unit_value)]
+    fn from_der_key(der_key: &Block<D::Core>) -> Self {
+        let () = Self::OUTPUT_SIZE_CHECK;
+        let mut ipad_key = xor_key(der_key, IPAD);
+        let mut ipad_digest = D::Core::default();
+        ipad_digest.update_blocks(core::slice::from_ref(&ipad_key));
+        wipe(&mut ipad_key);
+        let mut opad_key = xor_key(der_key, OPAD);
+        let mut opad_digest = D::Core::default();
+        opad_digest.update_blocks(core::slice::from_ref(&opad_key));
+        wipe(&mut opad_key);
+        Self {
+            digest: ipad_digest.clone(),
+            ipad_digest,
+            opad_digest,
+        }
+    }
+}
+
+#[cfg(feature = "core-api")]
+impl<D> Clone for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            digest: self.digest.clone(),
+            ipad_digest: self.ipad_digest.clone(),
+            opad_digest: self.opad_digest.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "core-api")]
+impl<D> MacMarker for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+}
+
+#[cfg(feature = "core-api")]
+impl<D> BlockSizeUser for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+    type Block = Block<D::Core>;
+}
+
+#[cfg(feature = "core-api")]
+impl<D> BufferKindUser for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+    type BufferKind = <D::Core as BufferKindUser>::BufferKind;
+}
+
+#[cfg(feature = "core-api")]
+impl<D> KeySizeUser for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+    type Key = Block<D::Core>;
+    const KEY_SIZES: Lengths = Lengths::ANY;
+}
+
+#[cfg(feature = "core-api")]
+impl<D> OutputSizeUser for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+    type Output = <D::Core as OutputSizeUser>::Output;
+}
+
+#[cfg(feature = "core-api")]
+impl<D> KeyInit for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+    #[inline]
+    fn new(mut key: Self::Key) -> Self {
+        let res = Self::from_der_key(&key);
+        wipe(&mut key);
+        res
+    }
+
+    #[inline]
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        let mut der_key = get_der_key::<CoreWrapper<D::Core>>(key);
+        let res = Self::from_der_key(&der_key);
+        wipe(&mut der_key);
+        Ok(res)
+    }
+}
+
+#[cfg(feature = "core-api")]
+impl<D> UpdateCore for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+    #[inline(always)]
+    fn update_blocks(&mut self, blocks: &[Self::Block]) {
+        self.digest.update_blocks(blocks);
+    }
+}
+
+#[cfg(feature = "core-api")]
+impl<D> FixedOutputCore for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+    #[inline]
+    fn finalize_fixed_core(&mut self, buffer: &mut Buffer<Self>, out: &mut Self::Output) {
+        let mut inner_hash = Self::Output::zero();
+        self.digest.finalize_fixed_core(buffer, &mut inner_hash);
+        let outer = CoreWrapper::from_core(self.opad_digest.clone()).chain(inner_hash);
+        FixedOutput::finalize_into(outer, out);
+    }
+}
+
+#[cfg(feature = "core-api")]
+impl<D> Reset for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone,
+{
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.digest = self.ipad_digest.clone();
+    }
+}
+
+#[cfg(feature = "core-api")]
+impl<D> AlgorithmName for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone + AlgorithmName,
+{
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Hmac<")?;
+        <D::Core as AlgorithmName>::write_alg_name(f)?;
+        f.write_str(">")
+    }
+}
+
+#[cfg(feature = "core-api")]
+impl<D> fmt::Debug for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone + AlgorithmName,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HmacCore<")?;
+        <D::Core as AlgorithmName>::write_alg_name(f)?;
+        f.write_str("> { .. }")
+    }
+}
+
+#[cfg(all(feature = "core-api", feature = "zeroize"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<D> ZeroizeOnDrop for HmacCore<D>
+where
+    D: CoreProxy,
+    D::Core: HashMarker + UpdateCore + FixedOutputCore + Default + Clone + ZeroizeOnDrop,
+{
+}
+
+/// Simplified HMAC over any hash function implementing [`Digest`]
+/// and [`BlockSizeUser`].
+///
+/// Prefer [`Hmac`] for hash functions implemented using [`CoreWrapper`].
+#[derive(Clone)]
+pub struct SimpleHmac<D: Digest + BlockSizeUser> {
+    digest: D,
+    ipad_key: Block<D>,
+    opad_key: Block<D>,
+}
+
+impl<D: Digest + BlockSizeUser> SimpleHmac<D> {
+    const OUTPUT_SIZE_CHECK: () = assert!(
+        <D::Output as Array>::LEN <= <D::Block as Array>::LEN,
+        "HMAC requires hash output size not bigger than its block size"
+    );
+
+    #[allow(clippy::let_unit_value)]
+    fn from_der_key(der_key: &Block<D>) -> Self {
+        let () = Self::OUTPUT_SIZE_CHECK;
+        let ipad_key = xor_key(der_key, IPAD);
+        Self {
+            digest: D::new_with_prefix(ipad_key),
+            ipad_key,
+            opad_key: xor_key(der_key, OPAD),
+        }
+    }
+
+    /// Compute outer hash over the result of the inner `digest`.
+    #[inline]
+    fn finalize_outer(&self, digest: D, out: &mut D::Output) {
+        D::new_with_prefix(self.opad_key)
+            .chain_update(digest.finalize())
+            .finalize_into(out);
+    }
+}
+
+impl<D: Digest + BlockSizeUser> MacMarker for SimpleHmac<D> {}
+
+impl<D: Digest + BlockSizeUser> KeySizeUser for SimpleHmac<D> {
+    type Key = Block<D>;
+    const KEY_SIZES: Lengths = Lengths::ANY;
+}
+
+impl<D: Digest + BlockSizeUser> KeyInit for SimpleHmac<D> {
+    #[inline]
+    fn new(mut key: Self::Key) -> Self {
+        let res = Self::from_der_key(&key);
+        wipe(&mut key);
+        res
+    }
+
+    #[inline]
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        let mut der_key = get_der_key::<D>(key);
+        let res = Self::from_der_key(&der_key);
+        wipe(&mut der_key);
+        Ok(res)
+    }
+}
+
+impl<D: Digest + BlockSizeUser> Update for SimpleHmac<D> {
+    #[inline(always)]
+    fn update(&mut self, data: &[u8]) {
+        self.digest.update(data);
+    }
+}
+
+impl<D: Digest + BlockSizeUser> OutputSizeUser for SimpleHmac<D> {
+    type Output = D::Output;
+}
+
+impl<D: Digest + BlockSizeUser> FixedOutput for SimpleHmac<D> {
+    #[inline]
+    fn finalize_into(mut self, out: &mut Self::Output) {
+        let digest = core::mem::replace(&mut self.digest, D::new());
+        self.finalize_outer(digest, out);
+    }
+}
+
+impl<D: Digest + BlockSizeUser> Reset for SimpleHmac<D> {
+    #[inline]
+    fn reset(&mut self) {
+        self.digest = D::new_with_prefix(self.ipad_key);
+    }
+}
+
+impl<D: Digest + BlockSizeUser> FixedOutputReset for SimpleHmac<D> {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut Self::Output) {
+        let digest = core::mem::replace(&mut self.digest, D::new_with_prefix(self.ipad_key));
+        self.finalize_outer(digest, out);
+    }
+}
+
+impl<D: Digest + BlockSizeUser + fmt::Debug> fmt::Debug for SimpleHmac<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SimpleHmac")
+            .field("digest", &self.digest)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<D: Digest + BlockSizeUser> Drop for SimpleHmac<D> {
+    #[inline]
+    fn drop(&mut self) {
+        self.ipad_key.as_mut().zeroize();
+        self.opad_key.as_mut().zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+impl<D: Digest + BlockSizeUser + ZeroizeOnDrop> ZeroizeOnDrop for SimpleHmac<D> {}
@@ -0,0 +1,210 @@
+//! Adapters which hash data passing through readers and writers.
+
+use crate::Update;
+use std::io;
+
+#[cfg(feature = "async-io")]
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+#[cfg(feature = "async-io")]
+use futures_io::{AsyncRead, AsyncWrite};
+
+/// Get part of `buf` processed by the inner reader or writer, which
+/// reported `n` processed bytes.
+///
+/// Returns an error instead of panicking if `n` is bigger than length
+/// of `buf`.
+#[inline]
+fn processed(buf: &[u8], n: usize) -> io::Result<&[u8]> {
+    buf.get(..n).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "inner reader or writer reported more bytes than buffer length",
+        )
+    })
+}
+
+/// Reader adapter which passes all data read from the inner reader
+/// to a hasher.
+///
+/// The adapter is generic over [`Update`], so it can be used with both hash
+/// functions and MACs. With enabled `async-io` feature it also implements
+/// `AsyncRead` from `futures-io` for [`Unpin`] readers. Tokio's `AsyncRead`
+/// is not implemented, tokio readers can be adapted using the `compat`
+/// module of the `tokio-util` crate.
+///
+/// ```
+/// use digest::{HashReader, Update};
+/// use std::io::Read;
+///
+/// #[derive(Default)]
+/// struct Counter(usize);
+///
+/// impl Update for Counter {
+///     fn update(&mut self, data: &[u8]) {
+///         self.0 += data.len();
+///     }
+/// }
+///
+/// let mut reader = HashReader::new(&b"hello world"[..], Counter::default());
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf).unwrap();
+/// let (_, counter) = reader.into_parts();
+/// assert_eq!(counter.0, 11);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HashReader<R, H> {
+    inner: R,
+    hasher: H,
+}
+
+impl<R, H: Update> HashReader<R, H> {
+    /// Wrap `inner` reader and pass data read from it to `hasher`.
+    #[inline]
+    pub fn new(inner: R, hasher: H) -> Self {
+        Self { inner, hasher }
+    }
+
+    /// Get reference to the inner reader.
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get mutable reference to the inner reader.
+    ///
+    /// Data read directly from the inner reader bypasses the hasher.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Get reference to the hasher.
+    #[inline]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Decompose adapter into the inner reader and the hasher.
+    #[inline]
+    pub fn into_parts(self) -> (R, H) {
+        (self.inner, self.hasher)
+    }
+}
+
+impl<R: io::Read, H: Update> io::Read for HashReader<R, H> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(processed(buf, n)?);
+        Ok(n)
+    }
+}
+
+/// Writer adapter which passes all data accepted by the inner writer
+/// to a hasher.
+///
+/// The adapter is generic over [`Update`], so it can be used with both hash
+/// functions and MACs. With enabled `async-io` feature it also implements
+/// `AsyncWrite` from `futures-io` for [`Unpin`] writers. Tokio's `AsyncWrite`
+/// is not implemented, tokio writers can be adapted using the `compat`
+/// module of the `tokio-util` crate.
+#[derive(Clone, Debug)]
+pub struct HashWriter<W, H> {
+    inner: W,
+    hasher: H,
+}
+
+impl<W, H: Update> HashWriter<W, H> {
+    /// Wrap `inner` writer and pass data written into it to `hasher`.
+    #[inline]
+    pub fn new(inner: W, hasher: H) -> Self {
+        Self { inner, hasher }
+    }
+
+    /// Get reference to the inner writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get mutable reference to the inner writer.
+    ///
+    /// Data written directly into the inner writer bypasses the hasher.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Get reference to the hasher.
+    #[inline]
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Decompose adapter into the inner writer and the hasher.
+    #[inline]
+    pub fn into_parts(self) -> (W, H) {
+        (self.inner, self.hasher)
+    }
+}
+
+impl<W: io::Write, H: Update> io::Write for HashWriter<W, H> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(processed(buf, n)?);
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(feature = "async-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async-io")))]
+impl<R: AsyncRead + Unpin, H: Update + Unpin> AsyncRead for HashReader<R, H> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let Self { inner, hasher } = self.get_mut();
+        let n = match Pin::new(inner).poll_read(cx, buf) {
+            Poll::Ready(res) => res?,
+            Poll::Pending => return Poll::Pending,
+        };
+        hasher.update(processed(buf, n)?);
+        Poll::Ready(Ok(n))
+    }
+}
+
+#[cfg(feature = "async-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async-io")))]
+impl<W: AsyncWrite + Unpin, H: Update + Unpin> AsyncWrite for HashWriter<W, H> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let Self { inner, hasher } = self.get_mut();
+        let n = match Pin::new(inner).poll_write(cx, buf) {
+            Poll::Ready(res) => res?,
+            Poll::Pending => return Poll::Pending,
+        };
+        hasher.update(processed(buf, n)?);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
@@ -0,0 +1,127 @@
+use crate::InvalidOutputSize;
+use array::Array;
+use core::{fmt, ops::Deref};
+
+/// Key derivation functions following the extract-then-expand paradigm,
+/// e.g. HKDF.
+///
+/// The extract step concentrates entropy of input keying material, e.g.
+/// an ECDH shared secret or a KEM output, into a pseudorandom key. The expand
+/// step derives output keying material of the requested length bound to
+/// the context information `info`, which may consist of several parts
+/// (see [`Info`]). Parts are processed as if they were concatenated.
+pub trait Kdf: Sized {
+    /// Maximum number of bytes which can be derived by a single
+    /// [`expand`][Self::expand] call.
+    const MAX_OUTPUT_SIZE: usize;
+
+    /// Extract pseudorandom key from input keying material `ikm`.
+    ///
+    /// Empty `salt` is equivalent to absence of salt.
+    fn extract(salt: &[u8], ikm: &[u8]) -> Self;
+
+    /// Expand pseudorandom key into `okm` using context information `info`.
+    ///
+    /// Returns `Err(InvalidOutputSize)` if `okm` is longer than
+    /// [`MAX_OUTPUT_SIZE`][Self::MAX_OUTPUT_SIZE].
+    fn expand(&self, info: &[&[u8]], okm: &mut [u8]) -> Result<(), InvalidOutputSize>;
+
+    /// Expand pseudorandom key into an array.
+    #[inline]
+    fn expand_array<A: Array>(&self, info: &[&[u8]]) -> Result<A, InvalidOutputSize> {
+        let mut okm = A::zero();
+        self.expand(info, okm.as_mut())?;
+        Ok(okm)
+    }
+
+    /// Derive `okm` from input keying material by performing both
+    /// extract and expand steps.
+    #[inline]
+    fn derive(
+        salt: &[u8],
+        ikm: &[u8],
+        info: &[&[u8]],
+        okm: &mut [u8],
+    ) -> Result<(), InvalidOutputSize> {
+        Self::extract(salt, ikm).expand(info, okm)
+    }
+
+    /// Derive an array from input keying material by performing both
+    /// extract and expand steps.
+    #[inline]
+    fn derive_array<A: Array>(
+        salt: &[u8],
+        ikm: &[u8],
+        info: &[&[u8]],
+    ) -> Result<A, InvalidOutputSize> {
+        Self::extract(salt, ikm).expand_array(info)
+    }
+}
+
+const MAX_INFO_PARTS: usize = 16;
+
+/// Builder of context information consisting of several parts.
+///
+/// Dereferences to a slice of parts, so it can be passed directly
+/// to [`Kdf`] methods.
+///
+/// ```
+/// use digest::Info;
+///
+/// let len = 32u16.to_be_bytes();
+/// let info = Info::new().part(b"my protocol v1").part(b"session key").part(&len);
+/// assert_eq!(info.len(), 3);
+/// assert_eq!(info[1], b"session key");
+/// ```
+#[derive(Copy, Clone, Default)]
+pub struct Info<'a> {
+    parts: [&'a [u8]; MAX_INFO_PARTS],
+    len: usize,
+}
+
+impl<'a> Info<'a> {
+    /// Maximum number of parts.
+    pub const MAX_PARTS: usize = MAX_INFO_PARTS;
+
+    /// Create empty context information.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            parts: [&[]; Self::MAX_PARTS],
+            len: 0,
+        }
+    }
+
+    /// Append `part` to the context information.
+    ///
+    /// # Panics
+    /// If the builder already contains [`MAX_PARTS`][Self::MAX_PARTS] parts.
+    #[inline]
+    pub fn part(mut self, part: &'a [u8]) -> Self {
+        assert!(self.len < Self::MAX_PARTS, "too many info parts");
+        self.parts[self.len] = part;
+        self.len += 1;
+        self
+    }
+
+    /// Get parts of the context information.
+    #[inline]
+    pub fn parts(&self) -> &[&'a [u8]] {
+        &self.parts[..self.len]
+    }
+}
+
+impl<'a> Deref for Info<'a> {
+    type Target = [&'a [u8]];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.parts()
+    }
+}
+
+impl<'a> fmt::Debug for Info<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.parts()).finish()
+    }
+}
@@ -3,13 +3,15 @@
 //!
 //! Traits in this repository are organized into the following levels:
 //!
-//! - **High-level convenience traits**: [`Digest`], [`DynDigest`], [`Mac`].
-//!   Wrappers around lower-level traits for most common use-cases. Users should
-//!   usually prefer using these traits.
+//! - **High-level convenience traits**: [`Digest`], [`DynDigest`],
+//!   [`DynExtendableOutput`], [`DynVariableOutput`], [`BatchDigest`], [`Mac`].
+//!   Wrappers around lower-level traits for most common use-cases. Users
+//!   should usually prefer using these traits.
 //! - **Mid-level traits**: [`Update`], [`FixedOutput`], [`FixedOutputReset`],
 //!   [`ExtendableOutput`], [`ExtendableOutputReset`], [`XofReader`],
-//!   [`VariableOutput`], [`Reset`], [`KeyInit`], and [`InnerInit`]. These
-//!   traits atomically describe available functionality of an algorithm.
+//!   [`XofReaderSeek`], [`VariableOutput`], [`Reset`], [`KeyInit`], [`InnerInit`], and
+//!   [`SerializableState`]. These traits atomically describe available
+//!   functionality of an algorithm.
 //! - **Marker traits**: [`HashMarker`], [`MacMarker`]. Used to distinguish
 //!   different algorithm classes.
 //! - **Low-level traits** defined in the [`core_api`] module. These traits
@@ -20,7 +22,20 @@
 //! Additionally hash functions implement traits from the standard library:
 //! [`Default`], [`Clone`], [`Write`][std::io::Write]. The latter is
 //! feature-gated behind `std` feature, which is usually enabled by default
-//! by hash implementation crates.
+//! by hash implementation crates. With the same feature [`HashReader`] and
+//! [`HashWriter`] adapters hash data passing through readers and writers.
+//!
+//! The [`merkle`] module provides a generic tree hashing mode with inclusion
+//! proofs on top of any [`Digest`] implementation. With the `mac` feature
+//! the [`hmac`] module provides a generic HMAC implementation, and
+//! the [`hkdf`] module implements HKDF on top of it using the [`Kdf`] trait.
+//! The [`combinators`] module builds concatenated, truncated and prefixed
+//! hash functions out of existing ones.
+//!
+//! Hash outputs can be formatted and parsed as hex or Base64 in constant time
+//! using the [`ArrayEncoding`] trait and the [`Hex`], [`Base64`] and
+//! [`Base64Url`] wrappers. With the `serde` feature enabled, outputs can be
+//! (de)serialized using `#[serde(with = "digest::array::serde")]`.
 
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -51,22 +66,47 @@ use alloc::boxed::Box;
 #[cfg_attr(docsrs, doc(cfg(feature = "dev")))]
 pub mod dev;
 
+pub mod combinators;
 #[cfg(feature = "core-api")]
 #[cfg_attr(docsrs, doc(cfg(feature = "core-api")))]
 pub mod core_api;
 mod digest;
 #[cfg(feature = "mac")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mac")))]
+pub mod hkdf;
+#[cfg(feature = "mac")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mac")))]
+pub mod hmac;
+#[cfg(feature = "std")]
+mod io;
+mod kdf;
+#[cfg(feature = "mac")]
 mod mac;
+pub mod merkle;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod registry;
 
+pub use array;
 #[cfg(feature = "core-api")]
 #[cfg_attr(docsrs, doc(cfg(feature = "core-api")))]
 pub use block_buffer;
+#[cfg(feature = "zeroize")]
+#[cfg_attr(docsrs, doc(cfg(feature = "zeroize")))]
+pub use block_buffer::zeroize;
 pub use crypto_common;
 
-pub use crate::digest::{Digest, DynDigest, HashMarker};
-pub use crypto_common::{OutputSizeUser, Reset};
+#[cfg(feature = "alloc")]
+pub use crate::digest::BatchDigest;
+pub use crate::digest::{Digest, DynDigest, DynExtendableOutput, DynVariableOutput, HashMarker};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use crate::io::{HashReader, HashWriter};
+pub use crate::kdf::{Info, Kdf};
+pub use array::{ArrayEncoding, Base64, Base64Url, DecodeError, Hex};
 #[cfg(feature = "mac")]
-pub use crypto_common::{InnerInit, InvalidLength, Key, KeyInit};
+pub use crypto_common::{InnerInit, InnerUser, InvalidLength, Key, KeyInit, Lengths};
+pub use crypto_common::{Output, OutputSizeUser, Reset};
 #[cfg(feature = "mac")]
 pub use mac::{CtOutput, Mac, MacError, MacMarker};
 
@@ -137,6 +177,38 @@ pub trait XofReader {
     }
 }
 
+/// Trait for XOF readers which support random access to the output.
+///
+/// Positions are measured in bytes from the start of the output stream.
+pub trait XofReaderSeek: XofReader {
+    /// Try to get current position in the output stream.
+    ///
+    /// Returns [`InvalidPosition`] if position can not be represented
+    /// by `u64`.
+    fn try_current_pos(&self) -> Result<u64, InvalidPosition>;
+
+    /// Try to seek to the given position in the output stream.
+    ///
+    /// Returns [`InvalidPosition`] if `pos` is not reachable by the reader.
+    fn try_seek(&mut self, pos: u64) -> Result<(), InvalidPosition>;
+
+    /// Get current position in the output stream.
+    ///
+    /// # Panics
+    /// If position can not be represented by `u64`.
+    fn current_pos(&self) -> u64 {
+        self.try_current_pos().unwrap()
+    }
+
+    /// Seek to the given position in the output stream.
+    ///
+    /// # Panics
+    /// If `pos` is not reachable by the reader.
+    fn seek(&mut self, pos: u64) {
+        self.try_seek(pos).unwrap()
+    }
+}
+
 /// Trait for hash functions with extendable-output (XOF).
 pub trait ExtendableOutput: Sized + Update {
     /// Reader
@@ -272,6 +344,37 @@ pub trait VariableOutputReset: VariableOutput + Reset {
     }
 }
 
+/// Trait for hashers whose state can be serialized into a portable byte
+/// representation and restored later, e.g. to resume hashing of a message
+/// in a different process.
+///
+/// The serialized state starts with a format version byte and does not
+/// depend on the target platform.
+pub trait SerializableState: Sized {
+    /// Size of the serialized state in bytes.
+    const SERIALIZED_STATE_SIZE: usize;
+
+    /// Write serialized state into `out`.
+    ///
+    /// Returns `Err(InvalidBufferSize)` if `out` size is not equal to
+    /// [`SERIALIZED_STATE_SIZE`][Self::SERIALIZED_STATE_SIZE].
+    fn serialize_state(&self, out: &mut [u8]) -> Result<(), InvalidBufferSize>;
+
+    /// Restore hasher from a state previously written by
+    /// [`serialize_state`][Self::serialize_state].
+    fn deserialize_state(state: &[u8]) -> Result<Self, InvalidState>;
+
+    /// Write serialized state into a boxed slice.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn serialize_state_boxed(&self) -> Box<[u8]> {
+        let mut buf = vec![0u8; Self::SERIALIZED_STATE_SIZE].into_boxed_slice();
+        self.serialize_state(&mut buf)
+            .expect("buf length is equal to SERIALIZED_STATE_SIZE");
+        buf
+    }
+}
+
 /// The error type used in variable hash traits.
 #[derive(Clone, Copy, Debug, Default)]
 pub struct InvalidOutputSize;
@@ -298,3 +401,32 @@ impl fmt::Display for InvalidBufferSize {
 
 #[cfg(feature = "std")]
 impl std::error::Error for InvalidBufferSize {}
+
+/// The error type returned when a serialized state can not be restored.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct InvalidState;
+
+impl fmt::Display for InvalidState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid serialized state")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for InvalidState {}
+
+/// The error type returned when a position in an output stream
+/// is not valid for the reader.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct InvalidPosition;
+
+impl fmt::Display for InvalidPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid output stream position")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for InvalidPosition {}
@@ -1,10 +1,11 @@
 use crate::{FixedOutput, FixedOutputReset, Update};
-use crypto_common::{InvalidLength, Key, KeyInit, Output, OutputSizeUser, Reset};
+use crypto_common::{array::Array, InvalidLength, Key, KeyInit, Output, OutputSizeUser, Reset};
 
 #[cfg(feature = "rand_core")]
 use crate::rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
+use array::serde::{Deserialize, Deserializer, Serialize, Serializer};
 use core::fmt;
-use crypto_common::typenum::Unsigned;
 use subtle::{Choice, ConstantTimeEq};
 
 /// Marker trait for Message Authentication algorithms.
@@ -30,6 +31,10 @@ pub trait Mac: OutputSizeUser + Sized {
         Self: KeyInit;
 
     /// Create new value from variable size key.
+    ///
+    /// Accepts keys with any length supported by the algorithm (see
+    /// [`KeySizeUser::KEY_SIZES`][crypto_common::KeySizeUser::KEY_SIZES]),
+    /// e.g. HMAC accepts keys of any length.
     fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength>
     where
         Self: KeyInit;
@@ -85,7 +90,7 @@ impl<T: Update + FixedOutput + MacMarker> Mac for T {
     where
         Self: KeyInit,
     {
-        KeyInit::new(key)
+        KeyInit::new(*key)
     }
 
     #[inline(always)]
@@ -140,10 +145,10 @@ impl<T: Update + FixedOutput + MacMarker> Mac for T {
     #[inline]
     fn verify_slice(self, tag: &[u8]) -> Result<(), MacError> {
         let n = tag.len();
-        if n != Self::OutputSize::USIZE {
+        if n != <Self::Output as Array>::LEN {
             return Err(MacError);
         }
-        let choice = self.finalize_fixed().ct_eq(tag);
+        let choice = self.finalize_fixed().as_ref().ct_eq(tag);
         if choice.unwrap_u8() == 1 {
             Ok(())
         } else {
@@ -153,10 +158,10 @@ impl<T: Update + FixedOutput + MacMarker> Mac for T {
 
     fn verify_truncated_left(self, tag: &[u8]) -> Result<(), MacError> {
         let n = tag.len();
-        if n == 0 || n > Self::OutputSize::USIZE {
+        if n == 0 || n > <Self::Output as Array>::LEN {
             return Err(MacError);
         }
-        let choice = self.finalize_fixed()[..n].ct_eq(tag);
+        let choice = self.finalize_fixed().as_ref()[..n].ct_eq(tag);
 
         if choice.unwrap_u8() == 1 {
             Ok(())
@@ -167,11 +172,11 @@ impl<T: Update + FixedOutput + MacMarker> Mac for T {
 
     fn verify_truncated_right(self, tag: &[u8]) -> Result<(), MacError> {
         let n = tag.len();
-        if n == 0 || n > Self::OutputSize::USIZE {
+        if n == 0 || n > <Self::Output as Array>::LEN {
             return Err(MacError);
         }
-        let m = Self::OutputSize::USIZE - n;
-        let choice = self.finalize_fixed()[m..].ct_eq(tag);
+        let m = <Self::Output as Array>::LEN - n;
+        let choice = self.finalize_fixed().as_ref()[m..].ct_eq(tag);
 
         if choice.unwrap_u8() == 1 {
             Ok(())
@@ -203,6 +208,10 @@ pub struct CtOutput<T: OutputSizeUser> {
 
 impl<T: OutputSizeUser> CtOutput<T> {
     /// Create a new [`CtOutput`] value.
+    ///
+    /// `From<Output<T>>` can not be implemented since `Output<T>` may be
+    /// equal to `CtOutput<T>` from the coherence point of view, so use this
+    /// method or the `From<&Output<T>>` impl instead.
     #[inline(always)]
     pub fn new(bytes: Output<T>) -> Self {
         Self { bytes }
@@ -215,24 +224,17 @@ impl<T: OutputSizeUser> CtOutput<T> {
     }
 }
 
-impl<T: OutputSizeUser> From<Output<T>> for CtOutput<T> {
-    #[inline(always)]
-    fn from(bytes: Output<T>) -> Self {
-        Self { bytes }
-    }
-}
-
 impl<'a, T: OutputSizeUser> From<&'a Output<T>> for CtOutput<T> {
     #[inline(always)]
     fn from(bytes: &'a Output<T>) -> Self {
-        bytes.clone().into()
+        Self::new(*bytes)
     }
 }
 
 impl<T: OutputSizeUser> ConstantTimeEq for CtOutput<T> {
     #[inline(always)]
     fn ct_eq(&self, other: &Self) -> Choice {
-        self.bytes.ct_eq(&other.bytes)
+        self.bytes.as_ref().ct_eq(other.bytes.as_ref())
     }
 }
 
@@ -245,6 +247,22 @@ impl<T: OutputSizeUser> PartialEq for CtOutput<T> {
 
 impl<T: OutputSizeUser> Eq for CtOutput<T> {}
 
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<T: OutputSizeUser> Serialize for CtOutput<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        array::serde::serialize(&self.bytes, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, T: OutputSizeUser> Deserialize<'de> for CtOutput<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        array::serde::deserialize(deserializer).map(Self::new)
+    }
+}
+
 /// Error type for when the [`Output`] of a [`Mac`]
 /// is not equal to the expected value.
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
@@ -0,0 +1,604 @@
+//! Merkle tree hashing over fixed-size leaves.
+//!
+//! Input is split into leaves of `LEAF_SIZE` bytes (the last leaf may be
+//! shorter) and hashed into a binary tree as defined in [RFC 6962]:
+//!
+//! - the empty input is hashed as `H("")`;
+//! - leaves are hashed as `H(0x00 || leaf)`;
+//! - nodes are hashed as `H(0x01 || left || right)`, where the left subtree
+//!   contains the largest power of two number of leaves smaller than
+//!   the total number of leaves.
+//!
+//! [`MerkleHasher`] computes the root hash in a streaming fashion using
+//! `O(log n)` memory. With enabled `alloc` feature [`MerkleTree`] additionally
+//! keeps leaf hashes, which allows generation of [`InclusionProof`]s.
+//!
+//! ```
+//! use digest::merkle::MerkleHasher;
+//! use digest::{FixedOutput, Update};
+//! # use digest::{HashMarker, OutputSizeUser};
+//! # #[derive(Clone, Default)]
+//! # struct Sha256(u32);
+//! # impl HashMarker for Sha256 {}
+//! # impl OutputSizeUser for Sha256 { type Output = [u8; 4]; }
+//! # impl Update for Sha256 {
+//! #     fn update(&mut self, data: &[u8]) {
+//! #         for &b in data { self.0 = self.0.rotate_left(5) ^ u32::from(b) }
+//! #     }
+//! # }
+//! # impl FixedOutput for Sha256 {
+//! #     fn finalize_into(self, out: &mut [u8; 4]) { *out = self.0.to_le_bytes() }
+//! # }
+//!
+//! let mut hasher = MerkleHasher::<Sha256, 8>::default();
+//! hasher.update(b"some data split into several leaves");
+//! assert_eq!(hasher.leaf_count(), 5);
+//! let root = hasher.finalize_fixed();
+//! ```
+//!
+//! [RFC 6962]: https://www.rfc-editor.org/rfc/rfc6962#section-2.1
+
+use crate::{Digest, FixedOutput, FixedOutputReset, HashMarker, Reset, Update};
+use core::fmt;
+use crypto_common::{Output, OutputSizeUser};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Domain separation prefix of leaf hashes.
+pub const LEAF_PREFIX: u8 = 0x00;
+/// Domain separation prefix of node hashes.
+pub const NODE_PREFIX: u8 = 0x01;
+
+/// Compute hash of a leaf, i.e. `H(0x00 || leaf)`.
+#[inline]
+pub fn leaf_hash<D: Digest>(leaf: &[u8]) -> Output<D> {
+    D::new_with_prefix([LEAF_PREFIX])
+        .chain_update(leaf)
+        .finalize()
+}
+
+/// Compute hash of a node, i.e. `H(0x01 || left || right)`.
+#[inline]
+pub fn node_hash<D: Digest>(left: &Output<D>, right: &Output<D>) -> Output<D> {
+    D::new_with_prefix([NODE_PREFIX])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+}
+
+/// Hasher of the leaf which is currently being filled.
+#[derive(Clone)]
+struct LeafHasher<D: Digest, const LEAF_SIZE: usize> {
+    hasher: D,
+    len: usize,
+}
+
+impl<D: Digest, const LEAF_SIZE: usize> LeafHasher<D, LEAF_SIZE> {
+    const LEAF_SIZE_CHECK: () = assert!(LEAF_SIZE != 0, "leaf size must not be zero");
+
+    #[inline]
+    #[allow(clippy::let_unit_value)]
+    fn new() -> Self {
+        let () = Self::LEAF_SIZE_CHECK;
+        Self {
+            hasher: D::new_with_prefix([LEAF_PREFIX]),
+            len: 0,
+        }
+    }
+
+    /// Process `data`, passing hashes of completed leaves to `leaf_fn`.
+    fn update(&mut self, mut data: &[u8], mut leaf_fn: impl FnMut(Output<D>)) {
+        while !data.is_empty() {
+            let n = core::cmp::min(LEAF_SIZE - self.len, data.len());
+            let (chunk, rest) = data.split_at(n);
+            self.hasher.update(chunk);
+            self.len += n;
+            data = rest;
+            if self.len == LEAF_SIZE {
+                leaf_fn(self.finish());
+            }
+        }
+    }
+
+    /// Finalize hash of the current leaf and start a new one.
+    #[inline]
+    fn finish(&mut self) -> Output<D> {
+        core::mem::replace(self, Self::new()).hasher.finalize()
+    }
+}
+
+/// Streaming Merkle tree hasher with leaves of `LEAF_SIZE` bytes.
+///
+/// The hasher stores only roots of completed perfect subtrees, so it does
+/// not require allocations. It implements [`Digest`] if `D` implements
+/// [`HashMarker`].
+///
+/// `LEAF_SIZE` must not be zero, otherwise compilation fails.
+#[derive(Clone)]
+pub struct MerkleHasher<D: Digest, const LEAF_SIZE: usize> {
+    leaf: LeafHasher<D, LEAF_SIZE>,
+    leaf_count: u64,
+    /// Root of the perfect subtree with `2^i` leaves for each bit `i` set
+    /// in `leaf_count`.
+    subtrees: [Output<D>; 64],
+}
+
+impl<D: Digest, const LEAF_SIZE: usize> MerkleHasher<D, LEAF_SIZE> {
+    /// Return number of leaves processed so far, including
+    /// the incomplete last leaf.
+    #[inline]
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count + (self.leaf.len != 0) as u64
+    }
+
+    fn push_leaf(subtrees: &mut [Output<D>; 64], leaf_count: &mut u64, mut hash: Output<D>) {
+        let mut level = 0;
+        while (*leaf_count >> level) & 1 == 1 {
+            hash = node_hash::<D>(&subtrees[level], &hash);
+            level += 1;
+        }
+        subtrees[level] = hash;
+        *leaf_count += 1;
+    }
+}
+
+impl<D: Digest, const LEAF_SIZE: usize> Default for MerkleHasher<D, LEAF_SIZE> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            leaf: LeafHasher::new(),
+            leaf_count: 0,
+            subtrees: [<Output<D> as array::Zero>::ZERO; 64],
+        }
+    }
+}
+
+impl<D: Digest + HashMarker, const LEAF_SIZE: usize> HashMarker for MerkleHasher<D, LEAF_SIZE> {}
+
+impl<D: Digest, const LEAF_SIZE: usize> OutputSizeUser for MerkleHasher<D, LEAF_SIZE> {
+    type Output = Output<D>;
+}
+
+impl<D: Digest, const LEAF_SIZE: usize> Update for MerkleHasher<D, LEAF_SIZE> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        let Self {
+            leaf,
+            leaf_count,
+            subtrees,
+        } = self;
+        leaf.update(data, |hash| Self::push_leaf(subtrees, leaf_count, hash));
+    }
+}
+
+impl<D: Digest, const LEAF_SIZE: usize> FixedOutput for MerkleHasher<D, LEAF_SIZE> {
+    fn finalize_into(mut self, out: &mut Self::Output) {
+        if self.leaf.len != 0 {
+            let hash = self.leaf.finish();
+            Self::push_leaf(&mut self.subtrees, &mut self.leaf_count, hash);
+        }
+        let n = self.leaf_count;
+        if n == 0 {
+            *out = D::new().finalize();
+            return;
+        }
+        // fold subtrees from the right-most (smallest) one
+        let first = n.trailing_zeros() as usize;
+        let mut root = self.subtrees[first];
+        for level in first + 1..64 {
+            if (n >> level) & 1 == 1 {
+                root = node_hash::<D>(&self.subtrees[level], &root);
+            }
+        }
+        *out = root;
+    }
+}
+
+impl<D: Digest, const LEAF_SIZE: usize> Reset for MerkleHasher<D, LEAF_SIZE> {
+    #[inline]
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl<D: Digest, const LEAF_SIZE: usize> FixedOutputReset for MerkleHasher<D, LEAF_SIZE> {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut Self::Output) {
+        core::mem::take(self).finalize_into(out);
+    }
+}
+
+impl<D: Digest, const LEAF_SIZE: usize> fmt::Debug for MerkleHasher<D, LEAF_SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MerkleHasher")
+            .field("leaf_size", &LEAF_SIZE)
+            .field("leaf_count", &self.leaf_count())
+            .finish()
+    }
+}
+
+/// Merkle tree with leaves of `LEAF_SIZE` bytes which keeps all leaf hashes.
+///
+/// Data is appended to the tree using the [`Update`] trait. Unlike
+/// [`MerkleHasher`], the tree can generate inclusion proofs, but it uses
+/// memory proportional to the number of leaves.
+///
+/// ```
+/// use digest::merkle::{leaf_hash, MerkleHasher, MerkleTree};
+/// use digest::{FixedOutput, Update};
+/// # use digest::{HashMarker, OutputSizeUser};
+/// # #[derive(Clone, Default)]
+/// # struct Sha256(u32);
+/// # impl HashMarker for Sha256 {}
+/// # impl OutputSizeUser for Sha256 { type Output = [u8; 4]; }
+/// # impl Update for Sha256 {
+/// #     fn update(&mut self, data: &[u8]) {
+/// #         for &b in data { self.0 = self.0.rotate_left(5) ^ u32::from(b) }
+/// #     }
+/// # }
+/// # impl FixedOutput for Sha256 {
+/// #     fn finalize_into(self, out: &mut [u8; 4]) { *out = self.0.to_le_bytes() }
+/// # }
+///
+/// let data = b"some data split into several leaves";
+///
+/// let mut tree = MerkleTree::<Sha256, 8>::default();
+/// tree.update(data);
+/// let root = tree.root();
+/// assert_eq!(root, MerkleHasher::<Sha256, 8>::default().chain(data).finalize_fixed());
+///
+/// let proof = tree.prove(2).unwrap();
+/// assert!(proof.verify(&leaf_hash::<Sha256>(&data[16..24]), &root));
+/// assert!(!proof.verify(&leaf_hash::<Sha256>(&data[8..16]), &root));
+/// ```
+///
+/// `LEAF_SIZE` must not be zero, otherwise compilation fails.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone)]
+pub struct MerkleTree<D: Digest, const LEAF_SIZE: usize> {
+    leaf: LeafHasher<D, LEAF_SIZE>,
+    leaves: Vec<Output<D>>,
+    /// Hashes of complete perfect subtrees, `nodes[k]` contains hashes
+    /// of subtrees with `2^(k + 1)` leaves.
+    nodes: Vec<Vec<Output<D>>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<D: Digest, const LEAF_SIZE: usize> MerkleTree<D, LEAF_SIZE> {
+    /// Return number of leaves appended so far, including
+    /// the incomplete last leaf.
+    #[inline]
+    pub fn leaf_count(&self) -> usize {
+        self.leaves.len() + (self.leaf.len != 0) as usize
+    }
+
+    /// Return hashes of the complete leaves.
+    ///
+    /// Hash of the incomplete last leaf is not included.
+    #[inline]
+    pub fn complete_leaf_hashes(&self) -> &[Output<D>] {
+        &self.leaves
+    }
+
+    /// Compute root hash of the current tree.
+    ///
+    /// Only hashes of subtrees which contain the incomplete last leaf are
+    /// computed, other subtree hashes are cached.
+    #[inline]
+    pub fn root(&self) -> Output<D>
+    where
+        D: Clone,
+    {
+        let last = self.last_leaf_hash();
+        self.tree_hash(last.as_ref())
+    }
+
+    /// Generate inclusion proof for the leaf with the given `index`.
+    ///
+    /// Returns `None` if `index` is not smaller than [`Self::leaf_count`].
+    pub fn prove(&self, index: usize) -> Option<InclusionProof<D>>
+    where
+        D: Clone,
+    {
+        let leaf_count = self.leaf_count();
+        if index >= leaf_count {
+            return None;
+        }
+        let last = self.last_leaf_hash();
+        let mut path = Vec::new();
+        self.build_path(last.as_ref(), index, 0, leaf_count, &mut path);
+        Some(InclusionProof {
+            index,
+            leaf_count,
+            path,
+        })
+    }
+
+    /// Append hash of a complete leaf and cache hashes of completed
+    /// perfect subtrees.
+    fn push_leaf(leaves: &mut Vec<Output<D>>, nodes: &mut Vec<Vec<Output<D>>>, hash: Output<D>) {
+        leaves.push(hash);
+        let mut len = leaves.len();
+        let mut level = 0;
+        while len & 1 == 0 {
+            let lower = if level == 0 {
+                &leaves[..]
+            } else {
+                &nodes[level - 1][..]
+            };
+            let hash = node_hash::<D>(&lower[len - 2], &lower[len - 1]);
+            if nodes.len() == level {
+                nodes.push(Vec::new());
+            }
+            nodes[level].push(hash);
+            len /= 2;
+            level += 1;
+        }
+    }
+
+    /// Compute hash of the incomplete last leaf, if any.
+    fn last_leaf_hash(&self) -> Option<Output<D>>
+    where
+        D: Clone,
+    {
+        if self.leaf.len != 0 {
+            Some(self.leaf.clone().finish())
+        } else {
+            None
+        }
+    }
+
+    /// Compute root hash of the tree with hash `last` of the incomplete
+    /// last leaf.
+    fn tree_hash(&self, last: Option<&Output<D>>) -> Output<D> {
+        let n = self.leaves.len() + last.is_some() as usize;
+        if n == 0 {
+            D::new().finalize()
+        } else {
+            self.range_hash(last, 0, n)
+        }
+    }
+
+    /// Compute hash of the subtree with `len` leaves starting at leaf `start`.
+    ///
+    /// `start` must be a multiple of the largest power of two smaller
+    /// than `len`, which holds for all subtrees of the tree.
+    fn range_hash(&self, last: Option<&Output<D>>, start: usize, len: usize) -> Output<D> {
+        if len.is_power_of_two() {
+            let level = len.trailing_zeros() as usize;
+            return self.perfect_hash(last, level, start >> level);
+        }
+        let k = split_point(len);
+        let left = self.range_hash(last, start, k);
+        let right = self.range_hash(last, start + k, len - k);
+        node_hash::<D>(&left, &right)
+    }
+
+    /// Compute hash of the perfect subtree with `2^level` leaves starting
+    /// at leaf `index << level`.
+    fn perfect_hash(&self, last: Option<&Output<D>>, level: usize, index: usize) -> Output<D> {
+        let cached = match level {
+            0 => self.leaves.get(index),
+            _ => self.nodes.get(level - 1).and_then(|l| l.get(index)),
+        };
+        match (cached, level, last) {
+            (Some(hash), _, _) => *hash,
+            // subtrees which are not cached contain the incomplete last leaf
+            (None, 0, Some(last)) => *last,
+            (None, 0, None) => unreachable!("subtree is outside of the tree"),
+            (None, _, _) => {
+                let left = self.perfect_hash(last, level - 1, 2 * index);
+                let right = self.perfect_hash(last, level - 1, 2 * index + 1);
+                node_hash::<D>(&left, &right)
+            }
+        }
+    }
+
+    /// Append audit path of the leaf with `index` in the subtree with `len`
+    /// leaves starting at leaf `start` to `path`.
+    fn build_path(
+        &self,
+        last: Option<&Output<D>>,
+        index: usize,
+        start: usize,
+        len: usize,
+        path: &mut Vec<Output<D>>,
+    ) {
+        if len <= 1 {
+            return;
+        }
+        let k = split_point(len);
+        if index < k {
+            self.build_path(last, index, start, k, path);
+            path.push(self.range_hash(last, start + k, len - k));
+        } else {
+            self.build_path(last, index - k, start + k, len - k, path);
+            path.push(self.range_hash(last, start, k));
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: Digest, const LEAF_SIZE: usize> Default for MerkleTree<D, LEAF_SIZE> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            leaf: LeafHasher::new(),
+            leaves: Vec::new(),
+            nodes: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: Digest + HashMarker, const LEAF_SIZE: usize> HashMarker for MerkleTree<D, LEAF_SIZE> {}
+
+#[cfg(feature = "alloc")]
+impl<D: Digest, const LEAF_SIZE: usize> OutputSizeUser for MerkleTree<D, LEAF_SIZE> {
+    type Output = Output<D>;
+}
+
+#[cfg(feature = "alloc")]
+impl<D: Digest, const LEAF_SIZE: usize> Update for MerkleTree<D, LEAF_SIZE> {
+    #[inline]
+    fn update(&mut self, data: &[u8]) {
+        let Self {
+            leaf,
+            leaves,
+            nodes,
+        } = self;
+        leaf.update(data, |hash| Self::push_leaf(leaves, nodes, hash));
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: Digest, const LEAF_SIZE: usize> FixedOutput for MerkleTree<D, LEAF_SIZE> {
+    #[inline]
+    fn finalize_into(mut self, out: &mut Self::Output) {
+        let last = if self.leaf.len != 0 {
+            Some(self.leaf.finish())
+        } else {
+            None
+        };
+        *out = self.tree_hash(last.as_ref());
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: Digest, const LEAF_SIZE: usize> Reset for MerkleTree<D, LEAF_SIZE> {
+    #[inline]
+    fn reset(&mut self) {
+        self.leaf = LeafHasher::new();
+        self.leaves.clear();
+        self.nodes.clear();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: Digest, const LEAF_SIZE: usize> FixedOutputReset for MerkleTree<D, LEAF_SIZE> {
+    #[inline]
+    fn finalize_into_reset(&mut self, out: &mut Self::Output) {
+        core::mem::take(self).finalize_into(out);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: Digest, const LEAF_SIZE: usize> fmt::Debug for MerkleTree<D, LEAF_SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MerkleTree")
+            .field("leaf_size", &LEAF_SIZE)
+            .field("leaf_count", &self.leaf_count())
+            .finish()
+    }
+}
+
+/// Proof of inclusion of a leaf into a Merkle tree.
+///
+/// The audit path is stored in the bottom-up order.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct InclusionProof<D: OutputSizeUser> {
+    index: usize,
+    leaf_count: usize,
+    path: Vec<Output<D>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<D: OutputSizeUser> InclusionProof<D> {
+    /// Create proof from its parts, e.g. received from a remote party.
+    #[inline]
+    pub fn new(index: usize, leaf_count: usize, path: Vec<Output<D>>) -> Self {
+        Self {
+            index,
+            leaf_count,
+            path,
+        }
+    }
+
+    /// Return index of the proven leaf.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Return number of leaves in the tree.
+    #[inline]
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
+    /// Return audit path, i.e. sibling hashes from the leaf to the root.
+    #[inline]
+    pub fn path(&self) -> &[Output<D>] {
+        &self.path
+    }
+
+    /// Verify that leaf with hash `leaf` is included into the tree
+    /// with the given `root` hash.
+    ///
+    /// Uses the verification algorithm from RFC 9162, section 2.1.3.2.
+    pub fn verify(&self, leaf: &Output<D>, root: &Output<D>) -> bool
+    where
+        D: Digest,
+    {
+        if self.index >= self.leaf_count {
+            return false;
+        }
+        let mut f_n = self.index;
+        let mut s_n = self.leaf_count - 1;
+        let mut hash = *leaf;
+        for sibling in &self.path {
+            if s_n == 0 {
+                return false;
+            }
+            if f_n & 1 == 1 || f_n == s_n {
+                hash = node_hash::<D>(sibling, &hash);
+                while f_n & 1 == 0 && f_n != 0 {
+                    f_n >>= 1;
+                    s_n >>= 1;
+                }
+            } else {
+                hash = node_hash::<D>(&hash, sibling);
+            }
+            f_n >>= 1;
+            s_n >>= 1;
+        }
+        s_n == 0 && hash.as_ref() == root.as_ref()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: OutputSizeUser> Clone for InclusionProof<D> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index,
+            leaf_count: self.leaf_count,
+            path: self.path.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<D: OutputSizeUser> fmt::Debug for InclusionProof<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InclusionProof")
+            .field("index", &self.index)
+            .field("leaf_count", &self.leaf_count)
+            .field(
+                "path",
+                &self.path.iter().map(AsRef::as_ref).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// Return the largest power of two smaller than `n`, where `n > 1`.
+#[cfg(feature = "alloc")]
+#[inline]
+fn split_point(n: usize) -> usize {
+    debug_assert!(n > 1);
+    1 << (usize::BITS - 1 - (n - 1).leading_zeros())
+}
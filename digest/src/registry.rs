@@ -0,0 +1,270 @@
+//! Lookup of hash functions by name or OID at run time.
+//!
+//! Algorithm crates provide [`DigestEntry`] constants, while applications
+//! collect entries of the algorithms they support into a static table and
+//! wrap it into a [`DigestRegistry`]. No global state is involved, so
+//! registries work in `no_std` environments with `alloc`.
+//!
+//! Entries are named by the [`AlgorithmName`] implementation of the hash
+//! function, additional names can be provided as aliases.
+//!
+//! ```
+//! use digest::registry::{DigestEntry, DigestRegistry, RegistryEntry};
+//! # use digest::{FixedOutput, FixedOutputReset, HashMarker, OutputSizeUser, Reset, Update};
+//! # use digest::crypto_common::AlgorithmName;
+//! # #[derive(Clone, Default)]
+//! # struct Sha256(u8);
+//! # impl HashMarker for Sha256 {}
+//! # impl OutputSizeUser for Sha256 { type Output = [u8; 32]; }
+//! # impl Update for Sha256 { fn update(&mut self, data: &[u8]) { self.0 ^= data.len() as u8 } }
+//! # impl FixedOutput for Sha256 { fn finalize_into(self, out: &mut [u8; 32]) { out[0] = self.0 } }
+//! # impl FixedOutputReset for Sha256 {
+//! #     fn finalize_into_reset(&mut self, out: &mut [u8; 32]) { out[0] = self.0; self.0 = 0 }
+//! # }
+//! # impl Reset for Sha256 { fn reset(&mut self) { self.0 = 0 } }
+//! # impl AlgorithmName for Sha256 {
+//! #     fn write_alg_name(f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+//! #         f.write_str("Sha256")
+//! #     }
+//! # }
+//!
+//! // usually provided by the algorithm crate
+//! const SHA256: DigestEntry = Sha256::DIGEST_ENTRY
+//!     .aliases(&["SHA-2-256"])
+//!     .oid("2.16.840.1.101.3.4.2.1");
+//!
+//! static REGISTRY: DigestRegistry<'static> = DigestRegistry::new(&[SHA256]);
+//!
+//! let mut hasher = REGISTRY.new_digest("sha-256").unwrap();
+//! hasher.update(b"hello");
+//! assert_eq!(hasher.output_size(), 32);
+//!
+//! assert_eq!(REGISTRY.find("sha2_256").unwrap().name().to_string(), "Sha256");
+//! assert!(REGISTRY.new_digest_by_oid("2.16.840.1.101.3.4.2.1").is_some());
+//! assert!(REGISTRY.new_digest("SHA-512").is_none());
+//! ```
+
+use crate::DynDigest;
+use alloc::boxed::Box;
+use core::fmt;
+use crypto_common::{AlgorithmInfo, AlgorithmName};
+
+/// Create boxed default instance of `D`.
+///
+/// Intended to be used as a [`DigestEntry`] constructor.
+pub fn new_boxed<D: DynDigest + Default + 'static>() -> Box<dyn DynDigest> {
+    Box::new(D::default())
+}
+
+/// Hash functions which can be described by a [`DigestEntry`].
+///
+/// Implemented for all hash functions which implement [`DynDigest`],
+/// [`AlgorithmName`] and [`Default`]. The associated constant allows
+/// to build entries in constant context.
+pub trait RegistryEntry {
+    /// Entry named by the [`AlgorithmName`] implementation without aliases
+    /// and OID.
+    const DIGEST_ENTRY: DigestEntry;
+}
+
+impl<D: DynDigest + AlgorithmName + Default + 'static> RegistryEntry for D {
+    const DIGEST_ENTRY: DigestEntry = DigestEntry {
+        write_name: D::write_alg_name,
+        aliases: &[],
+        oid: None,
+        constructor: new_boxed::<D>,
+    };
+}
+
+/// Registry entry describing a hash function.
+#[derive(Copy, Clone)]
+#[non_exhaustive]
+pub struct DigestEntry {
+    /// Function which writes algorithm name, i.e. [`AlgorithmName::write_alg_name`].
+    pub write_name: fn(&mut fmt::Formatter<'_>) -> fmt::Result,
+    /// Additional names of the algorithm, e.g. `"SHA-256"`.
+    pub aliases: &'static [&'static str],
+    /// ASN.1 object identifier in the dotted decimal notation.
+    pub oid: Option<&'static str>,
+    /// Function which creates new hasher instance.
+    pub constructor: fn() -> Box<dyn DynDigest>,
+}
+
+impl DigestEntry {
+    /// Create entry for hash function `D`.
+    ///
+    /// Equivalent to [`RegistryEntry::DIGEST_ENTRY`], which can be used
+    /// in constant context.
+    #[inline]
+    pub fn of<D: DynDigest + AlgorithmName + Default + 'static>() -> Self {
+        D::DIGEST_ENTRY
+    }
+
+    /// Set additional names of the algorithm.
+    #[inline]
+    pub const fn aliases(mut self, aliases: &'static [&'static str]) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Set ASN.1 object identifier.
+    #[inline]
+    pub const fn oid(mut self, oid: &'static str) -> Self {
+        self.oid = Some(oid);
+        self
+    }
+
+    /// Set identifiers defined in the algorithm metadata.
+    ///
+    /// Can be used with [`AlgorithmMetadata::METADATA`][crypto_common::AlgorithmMetadata].
+    #[inline]
+    pub const fn metadata(mut self, info: AlgorithmInfo) -> Self {
+        self.oid = info.oid;
+        self
+    }
+
+    /// Get algorithm name which implements [`Display`][fmt::Display].
+    #[inline]
+    pub fn name(&self) -> impl fmt::Display {
+        Name(self.write_name)
+    }
+
+    /// Create new hasher instance.
+    #[inline]
+    pub fn new_digest(&self) -> Box<dyn DynDigest> {
+        (self.constructor)()
+    }
+
+    /// Check whether `name` refers to this algorithm, either by its
+    /// [`AlgorithmName`] or by one of the aliases.
+    ///
+    /// Names are compared ignoring ASCII case, dashes and underscores,
+    /// e.g. `"sha384"` and `"SHA_384"` both match `"SHA-384"`.
+    pub fn matches_name(&self, name: &str) -> bool {
+        let mut matcher = NameMatcher::new(name);
+        // writing into the matcher never fails, while errors returned by
+        // `write_name` are treated as a mismatch
+        let res = fmt::write(&mut matcher, format_args!("{}", self.name()));
+        if res.is_ok() && matcher.is_match() {
+            return true;
+        }
+        self.aliases.iter().any(|alias| {
+            let mut matcher = NameMatcher::new(name);
+            matcher.compare(alias);
+            matcher.is_match()
+        })
+    }
+}
+
+impl fmt::Debug for DigestEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DigestEntry")
+            .field("name", &format_args!("{}", self.name()))
+            .field("aliases", &self.aliases)
+            .field("oid", &self.oid)
+            .finish()
+    }
+}
+
+struct Name(fn(&mut fmt::Formatter<'_>) -> fmt::Result);
+
+impl fmt::Display for Name {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+#[inline]
+fn is_separator(b: u8) -> bool {
+    matches!(b, b'-' | b'_')
+}
+
+/// Incremental comparison of a name with formatted output, which avoids
+/// allocating the formatted algorithm name.
+struct NameMatcher<'a> {
+    rest: &'a [u8],
+    matches: bool,
+}
+
+impl<'a> NameMatcher<'a> {
+    fn new(name: &'a str) -> Self {
+        Self {
+            rest: name.as_bytes(),
+            matches: true,
+        }
+    }
+
+    fn compare(&mut self, s: &str) {
+        for b in s.bytes().filter(|&b| !is_separator(b)) {
+            let rest = self.rest;
+            match rest.iter().position(|&c| !is_separator(c)) {
+                Some(i) if rest[i].eq_ignore_ascii_case(&b) => self.rest = &rest[i + 1..],
+                _ => {
+                    self.matches = false;
+                    return;
+                }
+            }
+        }
+    }
+
+    fn is_match(&self) -> bool {
+        self.matches && self.rest.iter().all(|&c| is_separator(c))
+    }
+}
+
+impl<'a> fmt::Write for NameMatcher<'a> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.matches {
+            self.compare(s);
+        }
+        Ok(())
+    }
+}
+
+/// Static table of hash functions which can be looked up by name or OID.
+#[derive(Copy, Clone, Debug)]
+pub struct DigestRegistry<'a> {
+    entries: &'a [DigestEntry],
+}
+
+impl<'a> DigestRegistry<'a> {
+    /// Create registry from a table of entries.
+    ///
+    /// If several entries match a name or OID, the first one is used.
+    #[inline]
+    pub const fn new(entries: &'a [DigestEntry]) -> Self {
+        Self { entries }
+    }
+
+    /// Iterate over registered entries.
+    #[inline]
+    pub fn entries(&self) -> impl Iterator<Item = &'a DigestEntry> {
+        self.entries.iter()
+    }
+
+    /// Find entry by algorithm name or alias.
+    ///
+    /// See [`DigestEntry::matches_name`] for the comparison rules.
+    pub fn find(&self, name: &str) -> Option<&'a DigestEntry> {
+        self.entries.iter().find(|e| e.matches_name(name))
+    }
+
+    /// Find entry by ASN.1 object identifier.
+    pub fn find_by_oid(&self, oid: &str) -> Option<&'a DigestEntry> {
+        self.entries.iter().find(|e| e.oid == Some(oid))
+    }
+
+    /// Create new hasher instance by algorithm name or alias.
+    #[inline]
+    pub fn new_digest(&self, name: &str) -> Option<Box<dyn DynDigest>> {
+        self.find(name).map(DigestEntry::new_digest)
+    }
+
+    /// Create new hasher instance by ASN.1 object identifier.
+    #[inline]
+    pub fn new_digest_by_oid(&self, oid: &str) -> Option<Box<dyn DynDigest>> {
+        self.find_by_oid(oid).map(DigestEntry::new_digest)
+    }
+}
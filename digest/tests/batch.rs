@@ -0,0 +1,129 @@
+//! Tests of multi-buffer hashing over a toy core which processes
+//! three states in parallel.
+#![cfg(all(feature = "core-api", feature = "alloc"))]
+
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use digest::block_buffer::{BufferKind, Eager, Lazy};
+use digest::core_api::{
+    BlockSizeUser, Buffer, BufferKindUser, CoreWrapper, FixedOutputCore, OutputSizeUser,
+    ParUpdateCore, UpdateCore,
+};
+use digest::{BatchDigest, Digest, HashMarker};
+
+/// Number of `update_par_blocks` calls.
+static PAR_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone)]
+struct ToyCore<K> {
+    state: u64,
+    blocks: u64,
+    _kind: PhantomData<K>,
+}
+
+impl<K> Default for ToyCore<K> {
+    fn default() -> Self {
+        Self {
+            state: 1,
+            blocks: 0,
+            _kind: PhantomData,
+        }
+    }
+}
+
+impl<K> ToyCore<K> {
+    fn compress(&mut self, block: &[u8; 8]) {
+        let v = u64::from_le_bytes(*block).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        self.state = self.state.rotate_left(7) ^ v;
+        self.blocks += 1;
+    }
+}
+
+impl<K> HashMarker for ToyCore<K> {}
+
+impl<K> BlockSizeUser for ToyCore<K> {
+    type Block = [u8; 8];
+}
+
+impl<K: BufferKind> BufferKindUser for ToyCore<K> {
+    type BufferKind = K;
+}
+
+impl<K> OutputSizeUser for ToyCore<K> {
+    type Output = [u8; 16];
+}
+
+impl<K> UpdateCore for ToyCore<K> {
+    fn update_blocks(&mut self, blocks: &[[u8; 8]]) {
+        blocks.iter().for_each(|block| self.compress(block));
+    }
+}
+
+impl<K: BufferKind> FixedOutputCore for ToyCore<K> {
+    fn finalize_fixed_core(&mut self, buffer: &mut Buffer<Self>, out: &mut [u8; 16]) {
+        buffer.zero_padding(|block, pos| {
+            self.compress(block);
+            self.state ^= pos as u64;
+        });
+        out[..8].copy_from_slice(&self.state.to_le_bytes());
+        out[8..].copy_from_slice(&self.blocks.to_le_bytes());
+    }
+}
+
+impl<K: BufferKind> ParUpdateCore for ToyCore<K> {
+    const PAR_STATES: usize = 3;
+
+    fn update_par_blocks(states: &mut [Self], blocks: &[[u8; 8]]) {
+        assert_eq!(states.len(), blocks.len());
+        assert!(!states.is_empty() && states.len() <= Self::PAR_STATES);
+        PAR_CALLS.fetch_add(1, Ordering::Relaxed);
+        for (state, block) in states.iter_mut().zip(blocks) {
+            state.compress(block);
+        }
+    }
+}
+
+/// Message lengths including empty messages and exact block multiples.
+const LENS: &[usize] = &[0, 1, 7, 8, 9, 16, 17, 64, 0, 100, 3, 24, 199, 8, 15];
+
+fn check_batch<K: BufferKind>() {
+    type Toy<K> = CoreWrapper<ToyCore<K>>;
+
+    let data: Vec<u8> = (0..200).map(|i| i as u8).collect();
+    let messages: Vec<&[u8]> = LENS.iter().map(|&len| &data[..len]).collect();
+
+    let calls = PAR_CALLS.load(Ordering::Relaxed);
+    let outputs = Toy::<K>::digest_batch(&messages);
+    assert!(PAR_CALLS.load(Ordering::Relaxed) > calls);
+    assert_eq!(outputs.len(), messages.len());
+    for (msg, out) in messages.iter().zip(&outputs) {
+        assert_eq!(*out, Toy::<K>::digest(msg), "message length: {}", msg.len());
+    }
+
+    // batches smaller than and equal to the number of parallel states
+    for n in 1..=3 {
+        let outputs = Toy::<K>::digest_batch(&messages[4..4 + n]);
+        for (msg, out) in messages[4..4 + n].iter().zip(&outputs) {
+            assert_eq!(*out, Toy::<K>::digest(msg));
+        }
+    }
+
+    assert!(Toy::<K>::digest_batch(&[]).is_empty());
+}
+
+#[test]
+fn batch_eager() {
+    check_batch::<Eager>();
+}
+
+#[test]
+fn batch_lazy() {
+    check_batch::<Lazy>();
+}
+
+#[test]
+#[should_panic]
+fn batch_length_mismatch() {
+    let mut out = [[0u8; 16]; 1];
+    CoreWrapper::<ToyCore<Eager>>::digest_batch_into(&[b"a", b"b"], &mut out);
+}
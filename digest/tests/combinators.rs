@@ -0,0 +1,143 @@
+//! Known-answer tests for the combinators built over SHA-256.
+#![cfg(feature = "core-api")]
+
+mod sha256;
+
+use digest::array::ArrayEncoding;
+use digest::combinators::{Concat, Prefixed, Truncated};
+use digest::{FixedOutput, FixedOutputReset, SerializableState, Update};
+use sha256::Sha256;
+
+/// SHA-224 initial hash values, FIPS 180-4, 5.3.2.
+const SHA224_IV: [u32; 8] = [
+    0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
+];
+
+/// SHA-224 built from SHA-256 with a distinct initial state.
+type Sha224 = Truncated<Prefixed<Sha256>, 28>;
+
+fn sha224() -> Sha224 {
+    // format version, core state (hash values and number of processed
+    // blocks), buffer position and buffer
+    let mut state = vec![1u8];
+    for v in SHA224_IV.iter() {
+        state.extend_from_slice(&v.to_be_bytes());
+    }
+    state.resize(Sha256::SERIALIZED_STATE_SIZE, 0);
+    Sha224::from_serialized_state(&state).unwrap()
+}
+
+fn sha256_kat(msg: &str) -> [u8; 32] {
+    let hex = match msg {
+        "" => "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        "abc" => "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        _ => unreachable!(),
+    };
+    <[u8; 32]>::from_hex(hex).unwrap()
+}
+
+fn sha224_kat(msg: &str) -> [u8; 28] {
+    let hex = match msg {
+        "" => "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f",
+        "abc" => "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+        _ => unreachable!(),
+    };
+    <[u8; 28]>::from_hex(hex).unwrap()
+}
+
+/// Check output of `h` for `msg` after hashing junk and resetting it,
+/// and repeated use through `finalize_fixed_reset`.
+fn check_reset<H>(mut h: H, msg: &str, expected: &[u8])
+where
+    H: Update + FixedOutputReset + Clone,
+{
+    let fresh = h.clone();
+
+    h.update(b"junk data which must not affect the result");
+    h.reset();
+    h.update(msg.as_bytes());
+    assert_eq!(h.clone().finalize_fixed().as_ref(), expected);
+    h.reset();
+
+    for _ in 0..3 {
+        h.update(msg.as_bytes());
+        assert_eq!(h.finalize_fixed_reset().as_ref(), expected);
+    }
+
+    let mut h = fresh;
+    h.update(msg.as_bytes());
+    assert_eq!(h.finalize_fixed().as_ref(), expected);
+}
+
+#[test]
+fn truncated() {
+    for &msg in &["", "abc"] {
+        let expected = sha256_kat(msg);
+        check_reset(Truncated::<Sha256, 32>::default(), msg, &expected);
+        check_reset(Truncated::<Sha256, 16>::default(), msg, &expected[..16]);
+        check_reset(Truncated::<Sha256, 1>::default(), msg, &expected[..1]);
+    }
+}
+
+#[test]
+fn truncated_initial_state() {
+    for &msg in &["", "abc"] {
+        let expected = sha224_kat(msg);
+        check_reset(sha224(), msg, &expected);
+
+        // plain truncation resets to the SHA-256 initial state
+        let mut h = Truncated::<Sha256, 28>::default();
+        h.update(msg.as_bytes());
+        assert_ne!(h.finalize_fixed(), expected);
+    }
+
+    assert!(Sha224::from_serialized_state(&[1, 2, 3]).is_err());
+}
+
+#[test]
+fn concat() {
+    type Both = Concat<Sha256, Sha224, 60>;
+
+    for &msg in &["", "abc"] {
+        let mut expected = sha256_kat(msg).to_vec();
+        expected.extend_from_slice(&sha224_kat(msg));
+        check_reset(Both::new(Sha256::default(), sha224()), msg, &expected);
+
+        let (a, b) = Both::new(Sha256::default(), sha224()).into_inner();
+        let mut a2 = a.clone();
+        a2.update(msg.as_bytes());
+        assert_eq!(a2.finalize_fixed(), sha256_kat(msg));
+        let mut b2 = b.clone();
+        b2.update(msg.as_bytes());
+        assert_eq!(b2.finalize_fixed(), sha224_kat(msg));
+    }
+}
+
+#[test]
+fn prefixed() {
+    // FIPS 180-4 two-block message
+    let msg = "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    let expected =
+        <[u8; 32]>::from_hex("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1")
+            .unwrap();
+
+    for split in 0..=msg.len() {
+        let (prefix, rest) = msg.split_at(split);
+        let h = Prefixed::<Sha256>::new(prefix.as_bytes());
+        check_reset(h.clone(), rest, &expected);
+
+        // the saved state has processed only the prefix
+        let mut state = h.prefix_state().clone();
+        state.update(rest.as_bytes());
+        assert_eq!(state.finalize_fixed(), expected);
+
+        let h2 = Prefixed::<Sha256>::from_hasher(h.prefix_state().clone());
+        check_reset(h2, rest, &expected);
+    }
+
+    let h = Prefixed::<Sha256>::new(b"ab");
+    let mut state = vec![0u8; Sha256::SERIALIZED_STATE_SIZE];
+    h.prefix_state().serialize_state(&mut state).unwrap();
+    let h = Prefixed::<Sha256>::from_serialized_state(&state).unwrap();
+    check_reset(h, "c", &sha256_kat("abc"));
+}
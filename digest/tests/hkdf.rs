@@ -0,0 +1,40 @@
+//! HKDF tests with test vectors from RFC 5869, Appendix A.
+#![cfg(all(feature = "core-api", feature = "dev", feature = "mac"))]
+
+mod sha256;
+
+use digest::array::ArrayEncoding;
+use digest::hkdf::Hkdf;
+use digest::hmac::{Hmac, SimpleHmac};
+use digest::{new_kdf_test, Kdf};
+use sha256::Sha256;
+
+new_kdf_test!(hkdf_hmac_sha256, "hkdf_sha256", Hkdf<Hmac<Sha256>>);
+new_kdf_test!(
+    hkdf_simple_hmac_sha256,
+    "hkdf_sha256",
+    Hkdf<SimpleHmac<Sha256>>
+);
+
+#[test]
+fn extract_prk() {
+    // RFC 5869, A.1
+    let ikm = [0x0b; 22];
+    let salt = <[u8; 13]>::from_hex("000102030405060708090a0b0c").unwrap();
+    let info = <[u8; 10]>::from_hex("f0f1f2f3f4f5f6f7f8f9").unwrap();
+    let prk =
+        <[u8; 32]>::from_hex("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")
+            .unwrap();
+    let okm = <[u8; 42]>::from_hex(
+        "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+    )
+    .unwrap();
+
+    let (res, hkdf) = Hkdf::<Hmac<Sha256>>::extract_prk(&salt, &ikm);
+    assert_eq!(res, prk);
+    assert_eq!(hkdf.expand_array::<[u8; 42]>(&[&info]).unwrap(), okm);
+
+    let hkdf = Hkdf::<Hmac<Sha256>>::from_prk(&prk).unwrap();
+    assert_eq!(hkdf.expand_array::<[u8; 42]>(&[&info]).unwrap(), okm);
+    assert!(Hkdf::<Hmac<Sha256>>::from_prk(&prk[..31]).is_err());
+}
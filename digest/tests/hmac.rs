@@ -0,0 +1,138 @@
+//! HMAC tests.
+#![cfg(all(feature = "core-api", feature = "mac"))]
+
+mod sha256;
+
+use digest::array::{Array, ArrayEncoding};
+use digest::hmac::{Hmac, SimpleHmac};
+use digest::{Digest, FixedOutputReset, KeyInit, Mac, Reset};
+use sha256::Sha256;
+
+/// Test vectors from RFC 4231 as `(key, data, tag)`.
+const RFC4231: &[(&[u8], &[u8], &str)] = &[
+    (
+        &[0x0b; 20],
+        b"Hi There",
+        "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+    ),
+    (
+        b"Jefe",
+        b"what do ya want for nothing?",
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+    ),
+    (
+        &[0xaa; 131],
+        b"Test Using Larger Than Block-Size Key - Hash Key First",
+        "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+    ),
+];
+
+/// Keys shorter than, equal to and longer than the block size.
+const KEY_LENS: &[usize] = &[0, 1, 13, 63, 64, 65, 100, 200];
+
+fn key(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (3 * i + 1) as u8).collect()
+}
+
+fn tag<M: Mac<Output = [u8; 32]> + KeyInit>(key: &[u8], data: &[u8]) -> [u8; 32] {
+    <M as Mac>::new_from_slice(key)
+        .unwrap()
+        .chain_update(data)
+        .finalize()
+        .into_bytes()
+}
+
+#[test]
+fn rfc4231() {
+    for &(key, data, expected) in RFC4231 {
+        let expected = <[u8; 32]>::from_hex(expected).unwrap();
+        assert_eq!(tag::<Hmac<Sha256>>(key, data), expected);
+        assert_eq!(tag::<SimpleHmac<Sha256>>(key, data), expected);
+    }
+}
+
+#[test]
+fn hmac_and_simple_hmac_agree() {
+    let data = b"some message which is longer than a single block of the hash function";
+    for &len in KEY_LENS {
+        let key = key(len);
+        for n in [0, 1, 63, 64, data.len()] {
+            assert_eq!(
+                tag::<Hmac<Sha256>>(&key, &data[..n]),
+                tag::<SimpleHmac<Sha256>>(&key, &data[..n]),
+                "key length: {}, data length: {}",
+                len,
+                n,
+            );
+        }
+    }
+}
+
+#[test]
+fn long_key_is_hashed() {
+    let data = b"message";
+    for &len in KEY_LENS.iter().filter(|&&len| len > 64) {
+        let key = key(len);
+        let hashed = Sha256::digest(&key);
+        assert_eq!(
+            tag::<Hmac<Sha256>>(&key, data),
+            tag::<Hmac<Sha256>>(&hashed, data),
+        );
+        assert_eq!(
+            tag::<SimpleHmac<Sha256>>(&key, data),
+            tag::<SimpleHmac<Sha256>>(&hashed, data),
+        );
+    }
+}
+
+#[test]
+fn key_of_block_size() {
+    let key = <[u8; 64]>::from_fn(|i| i as u8);
+    let data = b"message";
+    let expected =
+        <[u8; 32]>::from_hex("eb97f15211c02e38e3213edb045e8301601867c1cd49df01c687eb04f8909e98")
+            .unwrap();
+    assert_eq!(tag::<Hmac<Sha256>>(&key, data), expected);
+    assert_eq!(tag::<SimpleHmac<Sha256>>(&key, data), expected);
+    assert_eq!(
+        <Hmac<Sha256> as Mac>::new(&key)
+            .chain_update(data)
+            .finalize()
+            .into_bytes(),
+        expected,
+    );
+    assert_eq!(
+        <SimpleHmac<Sha256> as Mac>::new(&key)
+            .chain_update(data)
+            .finalize()
+            .into_bytes(),
+        expected,
+    );
+}
+
+fn check_finalize_reset<M>()
+where
+    M: Mac<Output = [u8; 32]> + KeyInit + FixedOutputReset + Reset,
+{
+    let data = b"some message which is longer than a single block of the hash function";
+    for &len in KEY_LENS {
+        let key = key(len);
+        let mut mac = <M as Mac>::new_from_slice(&key).unwrap();
+        for n in [0, 5, data.len()] {
+            Mac::update(&mut mac, &data[..n]);
+            let expected = tag::<M>(&key, &data[..n]);
+            assert_eq!(mac.finalize_reset().into_bytes(), expected);
+        }
+        // reset without finalization
+        Mac::update(&mut mac, b"discarded");
+        Mac::reset(&mut mac);
+        Mac::update(&mut mac, data);
+        assert_eq!(mac.finalize().into_bytes(), tag::<M>(&key, data));
+    }
+}
+
+#[test]
+fn finalize_reset() {
+    check_finalize_reset::<Hmac<Sha256>>();
+    check_finalize_reset::<SimpleHmac<Sha256>>();
+}
@@ -0,0 +1,241 @@
+//! Merkle tree tests with known-answer vectors from RFC 6962 reference
+//! implementation.
+#![cfg(all(feature = "core-api", feature = "alloc"))]
+
+mod sha256;
+
+use digest::merkle::{leaf_hash, InclusionProof, MerkleHasher, MerkleTree};
+use digest::{array::ArrayEncoding, Digest, FixedOutput, Update};
+use sha256::Sha256;
+
+fn h(s: &str) -> [u8; 32] {
+    <[u8; 32]>::from_hex(s).unwrap()
+}
+
+/// Leaves of the Certificate Transparency test tree.
+const CT_LEAVES: &[&[u8]] = &[
+    b"",
+    b"\x00",
+    b"\x10",
+    b"\x20\x21",
+    b"\x30\x31",
+    b"\x40\x41\x42\x43",
+    b"\x50\x51\x52\x53\x54\x55\x56\x57",
+    b"\x60\x61\x62\x63\x64\x65\x66\x67\x68\x69\x6a\x6b\x6c\x6d\x6e\x6f",
+];
+
+/// Root hashes of the Certificate Transparency test tree with
+/// the first `i + 1` leaves.
+const CT_ROOTS: &[&str] = &[
+    "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+    "fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125",
+    "aeb6bcfe274b70a14fb067a5e5578264db0fa9b51af5e0ba159158f329e06e77",
+    "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+    "4e3bbb1f7b478dcfe71fb631631519a3bca12c9aefca1612bfce4c13a86264d4",
+    "76e67dadbcdf1e10e1b74ddc608abd2f98dfb16fbce75277b5232a127f2087ef",
+    "ddb89be403809e325750d3d263cd78929c2942b7942a34b77e122c9594a74c8c",
+    "5dc9da79a70659a9ad559cb701ded9a2ab9d823aad2f4960cfe370eff4604328",
+];
+
+/// Inclusion proofs in the Certificate Transparency test tree as
+/// `(index, leaf_count, path)`.
+const CT_PROOFS: &[(usize, usize, &[&str])] = &[
+    (
+        0,
+        8,
+        &[
+            "96a296d224f285c67bee93c30f8a309157f0daa35dc5b87e410b78630a09cfc7",
+            "5f083f0a1a33ca076a95279832580db3e0ef4584bdff1f54c8a360f50de3031e",
+            "6b47aaf29ee3c2af9af889bc1fb9254dabd31177f16232dd6aab035ca39bf6e4",
+        ],
+    ),
+    (
+        5,
+        8,
+        &[
+            "bc1a0643b12e4d2d7c77918f44e0f4f79a838b6cf9ec5b5c283e1f4d88599e6b",
+            "ca854ea128ed050b41b35ffc1b87b8eb2bde461e9e3b5596ece6b9d5975a0ae0",
+            "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+        ],
+    ),
+    (
+        3,
+        7,
+        &[
+            "0298d122906dcfc10892cb53a73992fc5b9f493ea4c9badb27b791b4127a7fe7",
+            "fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125",
+            "837dbb152e9b079010717e84e865da4ebc0fa198a806d59d31bf15accef22d0e",
+        ],
+    ),
+    (
+        4,
+        5,
+        &["d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7"],
+    ),
+    (0, 1, &[]),
+];
+
+#[test]
+fn sha256() {
+    assert_eq!(
+        Sha256::digest(b""),
+        h("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+    );
+    assert_eq!(
+        Sha256::digest(b"abc"),
+        h("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+    );
+}
+
+#[test]
+fn ct_inclusion_proofs() {
+    for &(index, leaf_count, path) in CT_PROOFS {
+        let path = path.iter().map(|p| h(p)).collect();
+        let proof = InclusionProof::<Sha256>::new(index, leaf_count, path);
+        let leaf = leaf_hash::<Sha256>(CT_LEAVES[index]);
+        let root = h(CT_ROOTS[leaf_count - 1]);
+        assert!(proof.verify(&leaf, &root));
+
+        let wrong_leaf = leaf_hash::<Sha256>(b"wrong");
+        assert!(!proof.verify(&wrong_leaf, &root));
+        let wrong_root = h(CT_ROOTS[leaf_count % CT_ROOTS.len()]);
+        assert!(!proof.verify(&leaf, &wrong_root));
+    }
+}
+
+/// Roots of trees with 4-byte leaves over `0, 1, 2, ..`, as
+/// `(data_len, root)`.
+const ROOTS: &[(usize, &str)] = &[
+    (
+        0,
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+    ),
+    (
+        1,
+        "96a296d224f285c67bee93c30f8a309157f0daa35dc5b87e410b78630a09cfc7",
+    ),
+    (
+        4,
+        "a498efa8d0759e7b704095853db9bc1ef8cf65af37bed9d006c4b2917e695061",
+    ),
+    (
+        7,
+        "eed61971b1101984022d360f3a5fc2e13bcf6119d52ca489439563dd8ea2ef14",
+    ),
+    (
+        20,
+        "0a30d6148fc3d1c38bc90624148f2021ee9d4a72d2fdcb53dcba91e46237f6d0",
+    ),
+    (
+        28,
+        "6a648b436a7ba0966d61d03e31033b8e6a5d69dcbf73777afb508ab1f0260a63",
+    ),
+];
+
+/// Inclusion proofs in the tree with 4-byte leaves over `0, 1, .., 27`,
+/// i.e. with 7 leaves.
+const PROOFS_28: &[&[&str]] = &[
+    &[
+        "5ab0680027d25adbc39635ae41b3bfdbd9b3c4bd829899d1ba9c845155e604f0",
+        "d5ebbc39e8bd0dceb9f7120ba588f91a386714bf51e320373f667927231666da",
+        "6a6e3483ae06d8a9e51f8e358ad2ab664ff49d2ccaf73599fce97d68bb5ca8d7",
+    ],
+    &[
+        "a498efa8d0759e7b704095853db9bc1ef8cf65af37bed9d006c4b2917e695061",
+        "d5ebbc39e8bd0dceb9f7120ba588f91a386714bf51e320373f667927231666da",
+        "6a6e3483ae06d8a9e51f8e358ad2ab664ff49d2ccaf73599fce97d68bb5ca8d7",
+    ],
+    &[
+        "71605befdce28696a8061d8a7a933fc525dfb4c64bd0a3eb638497f027fdddd0",
+        "000134e55e67854e1a3d28a78c6392b02623c2505580b0f9d571e0b4d3e2257f",
+        "6a6e3483ae06d8a9e51f8e358ad2ab664ff49d2ccaf73599fce97d68bb5ca8d7",
+    ],
+    &[
+        "c0823dfcc2e52cdb6d8b73f1fddcc5f1a99bd9cb0541d54fc7bad9274dd5a557",
+        "000134e55e67854e1a3d28a78c6392b02623c2505580b0f9d571e0b4d3e2257f",
+        "6a6e3483ae06d8a9e51f8e358ad2ab664ff49d2ccaf73599fce97d68bb5ca8d7",
+    ],
+    &[
+        "7dfe7f5443db1260d420476a9f96f81a4e82630ff6588cd4560ee3c8c024a12f",
+        "b6dce93443a4f92a7c7809c20919aba61b0e196ffc2427e96c181b95a45556c5",
+        "516c43cb9e4f82fe70437703f0a41649c5fd963ba5f4be8b736dca20bae05bdd",
+    ],
+    &[
+        "f6e84feef4210778c6edba9694b7f8923e7d53f5e8c3af05433902357904b542",
+        "b6dce93443a4f92a7c7809c20919aba61b0e196ffc2427e96c181b95a45556c5",
+        "516c43cb9e4f82fe70437703f0a41649c5fd963ba5f4be8b736dca20bae05bdd",
+    ],
+    &[
+        "8390999938d7092596cb98a14e20612cb33ccaa267f8c5c555e18ee6a767e9dd",
+        "516c43cb9e4f82fe70437703f0a41649c5fd963ba5f4be8b736dca20bae05bdd",
+    ],
+];
+
+fn data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| i as u8).collect()
+}
+
+#[test]
+fn roots() {
+    for &(len, root) in ROOTS {
+        let root = h(root);
+        let data = data(len);
+        assert_eq!(MerkleHasher::<Sha256, 4>::digest(&data), root);
+        assert_eq!(MerkleTree::<Sha256, 4>::digest(&data), root);
+
+        // byte-by-byte updates
+        let mut hasher = MerkleHasher::<Sha256, 4>::default();
+        let mut tree = MerkleTree::<Sha256, 4>::default();
+        for b in data.chunks(1) {
+            Update::update(&mut hasher, b);
+            Update::update(&mut tree, b);
+        }
+        assert_eq!(tree.root(), root);
+        assert_eq!(hasher.finalize_fixed(), root);
+        assert_eq!(tree.finalize_fixed(), root);
+    }
+}
+
+#[test]
+fn proofs() {
+    let data = data(28);
+    let mut tree = MerkleTree::<Sha256, 4>::default();
+    // the last leaf is incomplete until the last byte is added
+    Update::update(&mut tree, &data[..27]);
+    assert_eq!(tree.leaf_count(), 7);
+    assert_ne!(tree.root(), h(ROOTS[5].1));
+    Update::update(&mut tree, &data[27..]);
+    assert_eq!(tree.leaf_count(), 7);
+
+    let root = tree.root();
+    assert_eq!(root, h(ROOTS[5].1));
+    for (index, expected) in PROOFS_28.iter().enumerate() {
+        let proof = tree.prove(index).unwrap();
+        let expected: Vec<[u8; 32]> = expected.iter().map(|p| h(p)).collect();
+        assert_eq!(proof.path(), &expected[..]);
+
+        let leaf = leaf_hash::<Sha256>(&data[4 * index..4 * index + 4]);
+        assert!(proof.verify(&leaf, &root));
+    }
+    assert!(tree.prove(7).is_none());
+}
+
+#[test]
+fn single_leaf() {
+    let root = MerkleTree::<Sha256, 4>::digest([0u8]);
+    assert_eq!(root, leaf_hash::<Sha256>(&[0]));
+
+    let mut tree = MerkleTree::<Sha256, 4>::default();
+    Update::update(&mut tree, &[0, 1, 2, 3]);
+    let proof = tree.prove(0).unwrap();
+    assert!(proof.path().is_empty());
+    assert!(proof.verify(&leaf_hash::<Sha256>(&[0, 1, 2, 3]), &tree.root()));
+}
+
+#[test]
+fn empty_tree() {
+    let tree = MerkleTree::<Sha256, 4>::default();
+    assert_eq!(tree.leaf_count(), 0);
+    assert_eq!(tree.root(), Sha256::digest(b""));
+    assert!(tree.prove(0).is_none());
+}
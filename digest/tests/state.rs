@@ -0,0 +1,223 @@
+//! Serialization of the core wrapper states over a toy core.
+#![cfg(all(feature = "core-api", feature = "alloc"))]
+
+mod toy;
+
+use digest::core_api::{CoreWrapper, CtVariableCoreWrapper, RtVariableCoreWrapper};
+use digest::{
+    ExtendableOutput, FixedOutput, InvalidBufferSize, InvalidState, SerializableState, Update,
+    VariableOutput, XofReader,
+};
+use toy::ToyCore;
+
+type Fixed = CoreWrapper<ToyCore>;
+type Ct = CoreWrapper<CtVariableCoreWrapper<ToyCore, 12>>;
+type Rt = RtVariableCoreWrapper<ToyCore>;
+type Reader = <Fixed as ExtendableOutput>::Reader;
+
+fn msg() -> Vec<u8> {
+    (0..43).map(|i| (i * 7) as u8).collect()
+}
+
+/// Check that hashing resumes with identical output after serializing
+/// the state at every split point of the message.
+fn check_resume<H>(new: impl Fn() -> H, finalize: impl Fn(H) -> Vec<u8>)
+where
+    H: SerializableState + Update,
+{
+    let msg = msg();
+    let mut h = new();
+    h.update(&msg);
+    let expected = finalize(h);
+
+    for i in 0..=msg.len() {
+        let mut h = new();
+        h.update(&msg[..i]);
+        let state = h.serialize_state_boxed();
+        assert_eq!(state.len(), H::SERIALIZED_STATE_SIZE);
+
+        let mut restored = H::deserialize_state(&state).unwrap();
+        // serialization is canonical
+        assert_eq!(restored.serialize_state_boxed(), state);
+
+        h.update(&msg[i..]);
+        restored.update(&msg[i..]);
+        assert_eq!(finalize(h), expected, "split at {}", i);
+        assert_eq!(finalize(restored), expected, "split at {}", i);
+    }
+}
+
+/// Check rejection of states with a wrong version or length and of buffers
+/// with an invalid cursor or non-zero padding. The block buffer state
+/// starts at `buf_offset`.
+fn check_reject<H>(new: impl Fn() -> H, buf_offset: usize)
+where
+    H: SerializableState + Update,
+{
+    let mut h = new();
+    h.update(b"abc");
+    let state = h.serialize_state_boxed();
+    assert!(H::deserialize_state(&state).is_ok());
+
+    let mut out = vec![0u8; H::SERIALIZED_STATE_SIZE + 1];
+    assert_eq!(h.serialize_state(&mut out).err(), Some(InvalidBufferSize));
+    assert_eq!(
+        h.serialize_state(&mut out[..H::SERIALIZED_STATE_SIZE - 1])
+            .err(),
+        Some(InvalidBufferSize)
+    );
+
+    let mut bad = state.to_vec();
+    bad[0] = 0;
+    assert_eq!(H::deserialize_state(&bad).err(), Some(InvalidState));
+    bad[0] = 2;
+    assert_eq!(H::deserialize_state(&bad).err(), Some(InvalidState));
+
+    assert_eq!(
+        H::deserialize_state(&state[..state.len() - 1]).err(),
+        Some(InvalidState)
+    );
+    let mut long = state.to_vec();
+    long.push(0);
+    assert_eq!(H::deserialize_state(&long).err(), Some(InvalidState));
+    assert_eq!(H::deserialize_state(&[]).err(), Some(InvalidState));
+
+    // buffered data: little-endian cursor followed by a zero-padded block
+    assert_eq!(state[buf_offset..buf_offset + 8], 3u64.to_le_bytes());
+    assert_eq!(state[buf_offset + 8..], [b'a', b'b', b'c', 0, 0, 0, 0, 0]);
+
+    // cursor equal to the block size is invalid for eager buffers
+    for &pos in &[8u64, 9, u64::MAX] {
+        let mut bad = state.to_vec();
+        bad[buf_offset..buf_offset + 8].copy_from_slice(&pos.to_le_bytes());
+        assert_eq!(H::deserialize_state(&bad).err(), Some(InvalidState));
+    }
+
+    // bytes past the cursor must be zero
+    for i in 3..8 {
+        let mut bad = state.to_vec();
+        bad[buf_offset + 8 + i] = 1;
+        assert_eq!(H::deserialize_state(&bad).err(), Some(InvalidState));
+    }
+}
+
+#[test]
+fn core_wrapper() {
+    check_resume(Fixed::default, |h| h.finalize_fixed().to_vec());
+    check_resume(Fixed::default, |h| {
+        let mut out = [0u8; 20];
+        h.finalize_xof().read(&mut out);
+        out.to_vec()
+    });
+    check_reject(Fixed::default, 17);
+}
+
+#[test]
+fn ct_variable_core_wrapper() {
+    check_resume(Ct::default, |h| h.finalize_fixed().to_vec());
+    check_reject(Ct::default, 17);
+
+    // output size is part of the initial core state
+    assert_ne!(
+        Ct::default().serialize_state_boxed(),
+        Fixed::default().serialize_state_boxed()
+    );
+}
+
+#[test]
+fn rt_variable_core_wrapper() {
+    for &n in &[4, 8, 16] {
+        check_resume(
+            || Rt::new(n).unwrap(),
+            |h| {
+                let mut out = vec![0u8; n];
+                h.finalize_variable(&mut out).unwrap();
+                out
+            },
+        );
+    }
+    check_reject(|| Rt::new(8).unwrap(), 25);
+
+    let state = Rt::new(8).unwrap().serialize_state_boxed();
+    assert_eq!(state[1..9], 8u64.to_le_bytes());
+    let restored = Rt::deserialize_state(&state).unwrap();
+    assert_eq!(restored.output_size(), 8);
+
+    // output sizes rejected by the core
+    for &n in &[0u64, 2, 6, 15, 20, 1 << 40] {
+        assert!(Rt::new(n as usize).is_err());
+        let mut bad = state.to_vec();
+        bad[1..9].copy_from_slice(&n.to_le_bytes());
+        assert_eq!(Rt::deserialize_state(&bad).err(), Some(InvalidState));
+    }
+}
+
+fn reader() -> Reader {
+    let mut h = Fixed::default();
+    h.update(b"toy xof");
+    h.finalize_xof()
+}
+
+#[test]
+fn xof_reader_core_wrapper() {
+    let mut expected = [0u8; 45];
+    reader().read(&mut expected);
+
+    for i in 0..=expected.len() {
+        let mut r = reader();
+        let mut out = [0u8; 45];
+        r.read(&mut out[..i]);
+        let state = r.serialize_state_boxed();
+        assert_eq!(state.len(), Reader::SERIALIZED_STATE_SIZE);
+
+        let mut restored = Reader::deserialize_state(&state).unwrap();
+        assert_eq!(restored.serialize_state_boxed(), state);
+        restored.read(&mut out[i..]);
+        assert_eq!(out, expected, "split at {}", i);
+    }
+}
+
+#[test]
+fn xof_reader_reject() {
+    // reader state: version, core state, cursor and the cached block
+    const POS: usize = 17;
+    const BLOCK: usize = 25;
+
+    let mut r = reader();
+    r.read(&mut [0u8; 3]);
+    let state = r.serialize_state_boxed();
+    assert!(Reader::deserialize_state(&state).is_ok());
+    assert_eq!(state[POS..BLOCK], 3u64.to_le_bytes());
+    // already read bytes are not stored
+    assert_eq!(state[BLOCK..BLOCK + 3], [0, 0, 0]);
+
+    let mut bad = state.to_vec();
+    bad[0] = 0;
+    assert_eq!(Reader::deserialize_state(&bad).err(), Some(InvalidState));
+    assert_eq!(
+        Reader::deserialize_state(&state[1..]).err(),
+        Some(InvalidState)
+    );
+
+    for &pos in &[8u64, 9, u64::MAX] {
+        let mut bad = state.to_vec();
+        bad[POS..BLOCK].copy_from_slice(&pos.to_le_bytes());
+        assert_eq!(Reader::deserialize_state(&bad).err(), Some(InvalidState));
+    }
+
+    // bytes before the cursor must be zero
+    for i in 0..3 {
+        let mut bad = state.to_vec();
+        bad[BLOCK + i] = 1;
+        assert_eq!(Reader::deserialize_state(&bad).err(), Some(InvalidState));
+    }
+
+    // buffer without unread bytes must be zero
+    let state = reader().serialize_state_boxed();
+    assert_eq!(state[POS..], [0u8; 16]);
+    for i in 0..8 {
+        let mut bad = state.to_vec();
+        bad[BLOCK + i] = 1;
+        assert_eq!(Reader::deserialize_state(&bad).err(), Some(InvalidState));
+    }
+}
@@ -0,0 +1,237 @@
+//! Toy hash core with fixed, variable and extendable output, used for
+//! testing the core wrappers. It is not a secure hash function.
+#![allow(dead_code)]
+
+use digest::block_buffer::Eager;
+use digest::core_api::{
+    AlgorithmName, BlockSizeUser, Buffer, BufferKindUser, ExtendableOutputCore, FixedOutputCore,
+    OutputSizeUser, Reset, SerializableStateCore, TruncSide, UpdateCore, VariableOutputCore,
+    XofReaderCore, XofReaderSeekCore,
+};
+#[cfg(feature = "zeroize")]
+use digest::zeroize::{Zeroize, ZeroizeOnDrop};
+use digest::{HashMarker, InvalidOutputSize, InvalidState};
+use std::fmt;
+
+const K: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Toy hash core with 8-byte blocks.
+///
+/// Variable output sizes must be multiples of 4 bytes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToyCore {
+    pub state: u64,
+    pub blocks: u64,
+}
+
+impl Default for ToyCore {
+    fn default() -> Self {
+        Self {
+            state: 1,
+            blocks: 0,
+        }
+    }
+}
+
+impl ToyCore {
+    fn compress(&mut self, block: &[u8; 8]) {
+        let v = u64::from_le_bytes(*block).wrapping_mul(K);
+        self.state = self.state.rotate_left(7) ^ v;
+        self.blocks += 1;
+    }
+
+    fn finalize(&mut self, buffer: &mut Buffer<Self>) -> [u8; 16] {
+        buffer.zero_padding(|block, pos| {
+            self.compress(block);
+            self.state ^= pos as u64;
+        });
+        let mut out = [0u8; 16];
+        out[..8].copy_from_slice(&self.state.to_le_bytes());
+        out[8..].copy_from_slice(&self.blocks.to_le_bytes());
+        out
+    }
+}
+
+impl HashMarker for ToyCore {}
+
+impl BlockSizeUser for ToyCore {
+    type Block = [u8; 8];
+}
+
+impl BufferKindUser for ToyCore {
+    type BufferKind = Eager;
+}
+
+impl OutputSizeUser for ToyCore {
+    type Output = [u8; 16];
+}
+
+impl UpdateCore for ToyCore {
+    fn update_blocks(&mut self, blocks: &[[u8; 8]]) {
+        blocks.iter().for_each(|block| self.compress(block));
+    }
+}
+
+impl FixedOutputCore for ToyCore {
+    fn finalize_fixed_core(&mut self, buffer: &mut Buffer<Self>, out: &mut [u8; 16]) {
+        *out = self.finalize(buffer);
+    }
+}
+
+impl VariableOutputCore for ToyCore {
+    const TRUNC_SIDE: TruncSide = TruncSide::Left;
+
+    fn new(output_size: usize) -> Result<Self, InvalidOutputSize> {
+        if !matches!(output_size, 4 | 8 | 12 | 16) {
+            return Err(InvalidOutputSize);
+        }
+        Ok(Self {
+            state: output_size as u64,
+            blocks: 0,
+        })
+    }
+
+    fn finalize_variable_core(&mut self, buffer: &mut Buffer<Self>, out: &mut [u8; 16]) {
+        *out = self.finalize(buffer);
+    }
+}
+
+impl ExtendableOutputCore for ToyCore {
+    type ReaderCore = ToyReaderCore;
+
+    fn finalize_xof_core(&mut self, buffer: &mut Buffer<Self>) -> ToyReaderCore {
+        let out = self.finalize(buffer);
+        let mut seed = [0u8; 8];
+        seed.copy_from_slice(&out[..8]);
+        ToyReaderCore::new(u64::from_le_bytes(seed))
+    }
+}
+
+impl Reset for ToyCore {
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl AlgorithmName for ToyCore {
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Toy")
+    }
+}
+
+impl SerializableStateCore for ToyCore {
+    type SerializedState = [u8; 16];
+
+    fn serialize_state_core(&self) -> [u8; 16] {
+        let mut res = [0u8; 16];
+        res[..8].copy_from_slice(&self.state.to_le_bytes());
+        res[8..].copy_from_slice(&self.blocks.to_le_bytes());
+        res
+    }
+
+    fn deserialize_state_core(state: &[u8; 16]) -> Result<Self, InvalidState> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&state[..8]);
+        let state_word = u64::from_le_bytes(buf);
+        buf.copy_from_slice(&state[8..]);
+        Ok(Self {
+            state: state_word,
+            blocks: u64::from_le_bytes(buf),
+        })
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for ToyCore {
+    fn zeroize(&mut self) {
+        self.state.zeroize();
+        self.blocks.zeroize();
+    }
+}
+
+/// Counter-based toy XOF reader core: block `i` is derived from
+/// the seed and `i`, so the output supports random access.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToyReaderCore {
+    pub seed: u64,
+    pub ctr: u64,
+}
+
+impl ToyReaderCore {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, ctr: 0 }
+    }
+
+    /// Output block with index `ctr`.
+    pub fn block(seed: u64, ctr: u64) -> [u8; 8] {
+        (seed ^ ctr.wrapping_mul(K)).rotate_left(29).to_le_bytes()
+    }
+}
+
+impl BlockSizeUser for ToyReaderCore {
+    type Block = [u8; 8];
+}
+
+impl XofReaderCore for ToyReaderCore {
+    fn read_block(&mut self) -> [u8; 8] {
+        let block = Self::block(self.seed, self.ctr);
+        self.ctr += 1;
+        block
+    }
+}
+
+impl XofReaderSeekCore for ToyReaderCore {
+    fn get_block_pos(&self) -> u64 {
+        self.ctr
+    }
+
+    fn set_block_pos(&mut self, pos: u64) {
+        self.ctr = pos;
+    }
+}
+
+impl AlgorithmName for ToyReaderCore {
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ToyReader")
+    }
+}
+
+impl SerializableStateCore for ToyReaderCore {
+    type SerializedState = [u8; 16];
+
+    fn serialize_state_core(&self) -> [u8; 16] {
+        let mut res = [0u8; 16];
+        res[..8].copy_from_slice(&self.seed.to_le_bytes());
+        res[8..].copy_from_slice(&self.ctr.to_le_bytes());
+        res
+    }
+
+    fn deserialize_state_core(state: &[u8; 16]) -> Result<Self, InvalidState> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&state[..8]);
+        let seed = u64::from_le_bytes(buf);
+        buf.copy_from_slice(&state[8..]);
+        Ok(Self {
+            seed,
+            ctr: u64::from_le_bytes(buf),
+        })
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for ToyReaderCore {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.ctr.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for ToyReaderCore {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for ToyReaderCore {}
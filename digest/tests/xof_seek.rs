@@ -0,0 +1,85 @@
+//! Seeking of the XOF reader wrapper over a toy counter-based core.
+#![cfg(feature = "core-api")]
+
+mod toy;
+
+use digest::core_api::CoreWrapper;
+use digest::{
+    ExtendableOutput, InvalidPosition, SerializableState, Update, XofReader, XofReaderSeek,
+};
+use toy::{ToyCore, ToyReaderCore};
+
+type Toy = CoreWrapper<ToyCore>;
+type Reader = <Toy as ExtendableOutput>::Reader;
+
+#[cfg(feature = "dev")]
+digest::xof_seek_test!(toy_xof_seek, Toy);
+
+fn reader() -> Reader {
+    let mut h = Toy::default();
+    h.update(b"xof seek test");
+    h.finalize_xof()
+}
+
+/// Output bytes `pos..pos + len` computed directly from the core.
+fn expected(seed: u64, pos: u64, len: usize) -> Vec<u8> {
+    let mut res = Vec::new();
+    let mut ctr = pos / 8;
+    while res.len() < (pos % 8) as usize + len {
+        res.extend_from_slice(&ToyReaderCore::block(seed, ctr));
+        ctr += 1;
+    }
+    res[(pos % 8) as usize..][..len].to_vec()
+}
+
+#[test]
+fn seek_offsets() {
+    // reader state starts with the format version and the core seed
+    let mut state = vec![0u8; Reader::SERIALIZED_STATE_SIZE];
+    reader().serialize_state(&mut state).unwrap();
+    let mut seed = [0u8; 8];
+    seed.copy_from_slice(&state[1..9]);
+    let seed = u64::from_le_bytes(seed);
+
+    let aligned = [0, 8, 16, 800, 1 << 32, u64::MAX - 7];
+    let unaligned = [1, 7, 9, 21, 803, (1 << 32) + 5, u64::MAX - 20];
+    for &pos in aligned.iter().chain(unaligned.iter()) {
+        let mut r = reader();
+        assert_eq!(r.try_seek(pos), Ok(()));
+        assert_eq!(r.try_current_pos(), Ok(pos), "pos {}", pos);
+
+        let len = if pos > u64::MAX - 20 { 7 } else { 20 };
+        let mut out = vec![0u8; len];
+        r.read(&mut out[..3]);
+        assert_eq!(r.try_current_pos(), Ok(pos + 3), "pos {}", pos);
+        r.read(&mut out[3..]);
+        assert_eq!(r.try_current_pos(), Ok(pos + len as u64), "pos {}", pos);
+        assert_eq!(out, expected(seed, pos, len), "pos {}", pos);
+    }
+}
+
+#[test]
+fn seek_end() {
+    let mut r = reader();
+    r.seek(u64::MAX);
+    assert_eq!(r.try_current_pos(), Ok(u64::MAX));
+    // the last byte of the last block
+    r.read(&mut [0u8; 1]);
+    assert_eq!(r.try_current_pos(), Err(InvalidPosition));
+}
+
+#[test]
+fn invalid_current_pos() {
+    // reader state: version, seed, block counter, cursor and cached block
+    let mut r = reader();
+    r.read(&mut [0u8; 3]);
+    let mut state = vec![0u8; Reader::SERIALIZED_STATE_SIZE];
+    r.serialize_state(&mut state).unwrap();
+    assert_eq!(state[9..17], 1u64.to_le_bytes());
+    assert_eq!(state[17..25], 3u64.to_le_bytes());
+
+    // cached block with block counter equal to zero
+    state[9..17].copy_from_slice(&0u64.to_le_bytes());
+    let r = Reader::deserialize_state(&state).unwrap();
+    assert_eq!(r.try_current_pos(), Err(InvalidPosition));
+}
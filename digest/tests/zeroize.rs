@@ -0,0 +1,79 @@
+//! Wiping of the core wrappers over a toy core.
+#![cfg(all(feature = "core-api", feature = "zeroize"))]
+
+mod toy;
+
+use digest::core_api::{CoreWrapper, CtVariableCoreWrapper, RtVariableCoreWrapper};
+use digest::zeroize::{Zeroize, ZeroizeOnDrop};
+use digest::{ExtendableOutput, SerializableState, Update, VariableOutput, XofReader};
+use toy::ToyCore;
+
+type Fixed = CoreWrapper<ToyCore>;
+type Ct = CoreWrapper<CtVariableCoreWrapper<ToyCore, 12>>;
+type Rt = RtVariableCoreWrapper<ToyCore>;
+type Reader = <Fixed as ExtendableOutput>::Reader;
+
+fn serialize<S: SerializableState>(s: &S) -> Vec<u8> {
+    let mut out = vec![0u8; S::SERIALIZED_STATE_SIZE];
+    s.serialize_state(&mut out).unwrap();
+    out
+}
+
+fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+
+#[test]
+fn core_wrapper() {
+    let mut h = Fixed::default();
+    h.update(b"secret data");
+    h.zeroize();
+    let (core, buffer) = h.decompose();
+    assert_eq!(
+        core,
+        ToyCore {
+            state: 0,
+            blocks: 0
+        }
+    );
+    assert_eq!(buffer.get_pos(), 0);
+    assert_eq!(buffer.get_block(), &[0u8; 8]);
+}
+
+#[test]
+fn ct_variable_core_wrapper() {
+    let mut h = Ct::default();
+    h.update(b"secret data");
+    h.zeroize();
+    let state = serialize(&h);
+    // only the format version remains
+    assert_eq!(state[0], 1);
+    assert!(state[1..].iter().all(|&b| b == 0));
+}
+
+#[test]
+fn rt_variable_core_wrapper() {
+    let mut h = Rt::new(8).unwrap();
+    h.update(b"secret data");
+    h.zeroize();
+    let state = serialize(&h);
+    // only the format version and the output size remain
+    assert_eq!(state[0], 1);
+    assert_eq!(state[1..9], 8u64.to_le_bytes());
+    assert!(state[9..].iter().all(|&b| b == 0));
+    assert_eq!(h.output_size(), 8);
+}
+
+#[test]
+fn xof_reader_core_wrapper() {
+    assert_zeroize_on_drop::<toy::ToyReaderCore>();
+    assert_zeroize_on_drop::<Reader>();
+
+    let mut h = Fixed::default();
+    h.update(b"secret data");
+    let mut r = h.finalize_xof();
+    r.read(&mut [0u8; 3]);
+    assert!(serialize(&r)[1..].iter().any(|&b| b != 0));
+    r.zeroize();
+    let state = serialize(&r);
+    assert_eq!(state[0], 1);
+    assert!(state[1..].iter().all(|&b| b == 0));
+}
@@ -18,6 +18,7 @@
 //! [Universal Hash Functions]: https://en.wikipedia.org/wiki/Universal_hashing
 
 #![no_std]
+#![cfg_attr(docsrs, feature(doc_cfg))]
 #![forbid(unsafe_code)]
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/RustCrypto/media/8f1a9894/logo.svg",
@@ -29,54 +30,65 @@
 #[cfg(feature = "std")]
 extern crate std;
 
-pub use generic_array::{self, typenum::consts};
+pub use array;
+pub use crypto_common::InvalidLength;
 
-use generic_array::typenum::Unsigned;
-use generic_array::{ArrayLength, GenericArray};
+use array::Array;
+use crypto_common::Lengths;
 use subtle::{Choice, ConstantTimeEq};
 
+#[cfg(feature = "serde")]
+use array::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 /// Keys to a [`UniversalHash`].
-pub type Key<U> = GenericArray<u8, <U as NewUniversalHash>::KeySize>;
+pub type Key<U> = <U as NewUniversalHash>::Key;
 
 /// Blocks are inputs to a [`UniversalHash`].
-pub type Block<U> = GenericArray<u8, <U as UniversalHash>::BlockSize>;
+pub type Block<U> = <U as UniversalHash>::Block;
 
 /// Instantiate a [`UniversalHash`] algorithm.
 pub trait NewUniversalHash: Sized {
-    /// Size of the key for the universal hash function.
-    type KeySize: ArrayLength<u8>;
+    /// Key array of the universal hash function.
+    type Key: Array;
 
     /// Instantiate a universal hash function with the given key.
     fn new(key: &Key<Self>) -> Self;
+
+    /// Instantiate a universal hash function with the given key slice.
+    ///
+    /// Default implementation will accept only keys with length equal to `Key::LEN`.
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        Self::Key::try_from_slice(key)
+            .map(Self::new)
+            .map_err(|_| InvalidLength::key(Lengths::fixed(Self::Key::LEN), key.len()))
+    }
 }
 
 /// The [`UniversalHash`] trait defines a generic interface for universal hash
 /// functions.
 pub trait UniversalHash: Clone {
-    /// Size of the inputs to and outputs from the universal hash function
-    type BlockSize: ArrayLength<u8>;
+    /// Inputs to and outputs from the universal hash function
+    type Block: Array;
 
     /// Input a block into the universal hash function
     fn update(&mut self, block: &Block<Self>);
 
     /// Input data into the universal hash function. If the length of the
     /// data is not a multiple of the block size, the remaining data is
-    /// padded with zeroes up to the `BlockSize`.
+    /// padded with zeroes up to the block size.
     ///
     /// This approach is frequently used by AEAD modes which use
     /// Message Authentication Codes (MACs) based on universal hashing.
     fn update_padded(&mut self, data: &[u8]) {
-        let mut chunks = data.chunks_exact(Self::BlockSize::to_usize());
+        let (blocks, rem) = Self::Block::slice_as_chunks(data);
 
-        for chunk in &mut chunks {
-            self.update(GenericArray::from_slice(chunk));
+        for block in blocks {
+            self.update(block);
         }
 
-        let rem = chunks.remainder();
-
         if !rem.is_empty() {
-            let mut padded_block = GenericArray::default();
-            padded_block[..rem.len()].copy_from_slice(rem);
+            let mut padded_block = Self::Block::zero();
+            padded_block.as_mut()[..rem.len()].copy_from_slice(rem);
             self.update(&padded_block);
         }
     }
@@ -113,7 +125,7 @@ pub trait UniversalHash: Clone {
 /// on universal hashing.
 #[derive(Clone)]
 pub struct Output<U: UniversalHash> {
-    bytes: GenericArray<u8, U::BlockSize>,
+    bytes: Block<U>,
 }
 
 impl<U> Output<U>
@@ -121,31 +133,26 @@ where
     U: UniversalHash,
 {
     /// Create a new [`Output`] block.
+    ///
+    /// `From<Block<U>>` can not be implemented since `Block<U>` may be
+    /// equal to `Output<U>` from the coherence point of view, so use this
+    /// method or the `From<&Block<U>>` impl instead.
     pub fn new(bytes: Block<U>) -> Output<U> {
         Output { bytes }
     }
 
-    /// Get the inner [`GenericArray`] this type wraps
+    /// Get the inner [`Block`] this type wraps
     pub fn into_bytes(self) -> Block<U> {
         self.bytes
     }
 }
 
-impl<U> From<Block<U>> for Output<U>
-where
-    U: UniversalHash,
-{
-    fn from(bytes: Block<U>) -> Self {
-        Output { bytes }
-    }
-}
-
 impl<'a, U> From<&'a Block<U>> for Output<U>
 where
     U: UniversalHash,
 {
     fn from(bytes: &'a Block<U>) -> Self {
-        bytes.clone().into()
+        Output::new(*bytes)
     }
 }
 
@@ -154,7 +161,7 @@ where
     U: UniversalHash,
 {
     fn ct_eq(&self, other: &Self) -> Choice {
-        self.bytes.ct_eq(&other.bytes)
+        self.bytes.as_ref().ct_eq(other.bytes.as_ref())
     }
 }
 
@@ -169,6 +176,22 @@ where
 
 impl<U: UniversalHash> Eq for Output<U> {}
 
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<U: UniversalHash> Serialize for Output<U> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        array::serde::serialize(&self.bytes, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, U: UniversalHash> Deserialize<'de> for Output<U> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        array::serde::deserialize(deserializer).map(Self::new)
+    }
+}
+
 /// Error type for when the [`Output`] of a [`UniversalHash`]
 /// is not equal to the expected value.
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]